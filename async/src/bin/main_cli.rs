@@ -1,13 +1,42 @@
 use clap::{arg, Command};
 use log::{error, info, LevelFilter};
+use serde_json::{json, Value};
 use simple_logger::SimpleLogger;
 
 use solana_gossip_async::errors::{Error, Result};
-use solana_gossip_async::{connection::Connection, handshake::handshake};
-use solana_gossip_proto::utils::parse_addr;
+use solana_gossip_async::{
+    client::{GossipClient, GossipEvent},
+    socket::GossipSocket,
+};
+use solana_gossip_proto::{
+    json::{CrdsValueLabelView, CrdsValueView, PruneDataView},
+    utils::{load_or_create_keypair, parse_addr, since_the_epoch_millis},
+};
+use solana_sdk::signature::Keypair;
+use tokio::sync::broadcast::error::RecvError;
+
+// the event in JSON, tagged by its variant name
+fn event_json(event: &GossipEvent) -> Value {
+    match event {
+        GossipEvent::Ping(pubkey) => json!({ "Ping": pubkey.to_string() }),
+        GossipEvent::Pong(pubkey) => json!({ "Pong": pubkey.to_string() }),
+        GossipEvent::Upserted(value) => {
+            json!({ "Upserted": CrdsValueView::from(value.as_ref()) })
+        }
+        GossipEvent::Purged(labels) => {
+            let labels = labels
+                .iter()
+                .map(CrdsValueLabelView::from)
+                .collect::<Vec<_>>();
+            json!({ "Purged": labels })
+        }
+        GossipEvent::Prune(data) => json!({ "Prune": PruneDataView::from(data.as_ref()) }),
+        GossipEvent::Unverified(pubkey) => json!({ "Unverified": pubkey.to_string() }),
+        GossipEvent::ShredVersion(shred_version) => json!({ "ShredVersion": shred_version }),
+    }
+}
 
 fn parse_socket_addr(value: &str) -> ::std::result::Result<std::net::SocketAddr, std::io::Error> {
-    println!("{value}");
     if let Some(addr) = parse_addr(value) {
         Ok(addr)
     } else {
@@ -32,23 +61,61 @@ async fn main() -> Result<()> {
                 .default_value("141.98.219.218:8000")
                 .value_parser(clap::builder::ValueParser::new(parse_socket_addr)),
         )
+        .arg(
+            arg!(--identity <PATH> "the node keypair file, generated if missing")
+                .required(false)
+                .value_parser(clap::value_parser!(std::path::PathBuf)),
+        )
+        .arg(
+            arg!(--"shred-version" <VALUE> "the cluster shred version, learned if missing")
+                .required(false)
+                .value_parser(clap::value_parser!(u16)),
+        )
+        .arg(arg!(--json "print the events as newline delimited JSON"))
         .get_matches();
 
+    let as_json = matches.get_flag("json");
+
     let Some(entrypoint_addr) = matches.get_one::<std::net::SocketAddr>("entrypoint") else {
         return Err(Error::InputError);
     };
 
-    let mut conn = Connection::connect(entrypoint_addr.to_owned()).await?;
+    let keypair = match matches.get_one::<std::path::PathBuf>("identity") {
+        Some(path) => load_or_create_keypair(path)?,
+        None => Keypair::new(),
+    };
+
+    let socket = GossipSocket::bind_any().await?;
 
-    let info = handshake(&mut conn).await;
-    match info {
-        Ok(Some(info)) => {
-            info!("OK {info:?}");
-        }
-        Err(err) => {
-            error!("exists with error:{err}")
+    let shred_version = matches
+        .get_one::<u16>("shred-version")
+        .copied()
+        .unwrap_or_default();
+
+    let client = GossipClient::spawn(socket, entrypoint_addr.to_owned(), keypair, shred_version);
+    info!("node pubkey:{}", client.pubkey());
+
+    let mut events_rx = client.subscribe();
+    loop {
+        match events_rx.recv().await {
+            Ok(event) if as_json => {
+                let line = json!({
+                    "timestamp": since_the_epoch_millis(),
+                    "event": event_json(&event),
+                });
+                println!("{line}");
+            }
+            Ok(GossipEvent::Upserted(value)) => {
+                info!("upserted {value} from:{}", value.pubkey());
+            }
+            Ok(event) => info!("{event:?}"),
+            Err(RecvError::Lagged(count)) => error!("lagged events #:{count}"),
+            Err(RecvError::Closed) => break,
         }
-        _ => {}
+    }
+
+    if let Err(err) = client.join().await {
+        error!("exists with error:{err}");
     }
 
     Ok(())
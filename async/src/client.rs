@@ -0,0 +1,398 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use log::{debug, info};
+use tokio::{
+    sync::{broadcast, RwLock},
+    task::JoinHandle,
+    time::interval,
+};
+
+use solana_gossip_proto::{
+    crds::{Crds, CrdsInsertion, CRDS_TIMEOUT_MS},
+    protocol::{
+        CrdsFilter, CrdsValue, CrdsValueLabel, LegacyContactInfo, Protocol, PruneData,
+        CRDS_FILTER_MAX_BYTES,
+    },
+    utils::{create_pong_response, create_pull_request, since_the_epoch_millis},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use crate::{
+    errors::{Error, Result},
+    socket::{Demux, GossipSocket},
+};
+
+const PULL_INTERVAL: u64 = 200; // 200msec
+const PURGE_INTERVAL: u64 = 1000; // 1sec
+const EVENTS_CAPACITY: usize = 1024;
+
+/// Decoded gossip traffic, delivered to the `GossipClient::subscribe` receivers.
+#[derive(Clone, Debug)]
+pub enum GossipEvent {
+    /// a verified ping has been answered with a pong
+    Ping(Pubkey),
+    Pong(Pubkey),
+    /// a value has been inserted or updated in the crds table
+    Upserted(Box<CrdsValue>),
+    /// values dropped from the crds table
+    Purged(Vec<CrdsValueLabel>),
+    Prune(Box<PruneData>),
+    /// a message or a value has been dropped due to an invalid signature
+    Unverified(Pubkey),
+    /// the cluster shred version learned from the entrypoint
+    ShredVersion(u16),
+}
+
+/// A gossip node running on its own tokio task: it pulls the entrypoint and
+/// the discovered peers periodically, answers pings and stores the received
+/// values in a crds table shared with the library users.
+pub struct GossipClient {
+    keypair: Arc<Keypair>,
+    crds: Arc<RwLock<Crds>>,
+    events_tx: broadcast::Sender<GossipEvent>,
+    task: JoinHandle<Result<()>>,
+}
+
+impl GossipClient {
+    /// A zero `shred_version` is learned from the entrypoint contact info.
+    pub fn spawn(
+        socket: GossipSocket,
+        entrypoint_addr: SocketAddr,
+        keypair: Keypair,
+        shred_version: u16,
+    ) -> Self {
+        let keypair = Arc::new(keypair);
+        let crds = Arc::new(RwLock::new(Crds::new()));
+        let (events_tx, _) = broadcast::channel(EVENTS_CAPACITY);
+
+        let task = tokio::spawn(run(
+            Demux::spawn(Arc::new(socket)),
+            entrypoint_addr,
+            keypair.clone(),
+            shred_version,
+            crds.clone(),
+            events_tx.clone(),
+        ));
+
+        Self {
+            keypair,
+            crds,
+            events_tx,
+            task,
+        }
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    pub fn crds(&self) -> Arc<RwLock<Crds>> {
+        self.crds.clone()
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<GossipEvent> {
+        self.events_tx.subscribe()
+    }
+
+    /// Waits for the task loop, which ends only on a socket error.
+    pub async fn join(self) -> Result<()> {
+        self.task.await?
+    }
+
+    pub async fn shutdown(self) {
+        self.task.abort();
+        let _ = self.task.await;
+    }
+}
+
+async fn run(
+    mut demux: Demux,
+    entrypoint_addr: SocketAddr,
+    keypair: Arc<Keypair>,
+    shred_version: u16,
+    crds: Arc<RwLock<Crds>>,
+    events_tx: broadcast::Sender<GossipEvent>,
+) -> Result<()> {
+    let socket = demux.socket();
+    let local_addr = socket.local_addr()?;
+    info!("local_addr:{local_addr:?} entrypoint_addr:{entrypoint_addr:?}");
+
+    let mut contact_info = LegacyContactInfo {
+        id: keypair.pubkey(),
+        gossip: local_addr,
+        wallclock: since_the_epoch_millis(),
+        shred_version,
+        ..LegacyContactInfo::default()
+    };
+
+    let mut filters: Vec<CrdsFilter> = Vec::new();
+    let mut peer_index: usize = 0;
+    let mut pull_interval = interval(Duration::from_millis(PULL_INTERVAL));
+    let mut purge_interval = interval(Duration::from_millis(PURGE_INTERVAL));
+
+    loop {
+        tokio::select! {
+            _ = pull_interval.tick() => {
+                // rotate through the filters, rebuilt from the values received so far
+                if filters.is_empty() {
+                    filters = crds.read().await.filters(CRDS_FILTER_MAX_BYTES);
+                }
+                let filter = filters.pop().unwrap_or_default();
+
+                // the entrypoint and, in turn, one of the discovered peers
+                let mut addrs = vec![entrypoint_addr];
+                let peers = gossip_peers(&*crds.read().await, &contact_info);
+                if !peers.is_empty() {
+                    peer_index = peer_index.wrapping_add(1);
+                    addrs.push(peers[peer_index % peers.len()]);
+                }
+
+                contact_info.wallclock = since_the_epoch_millis();
+                for addr in addrs {
+                    // a peer advertising an unreachable address must not stop the client
+                    let payload =
+                        create_pull_request(contact_info.clone(), filter.clone(), &keypair, addr);
+                    let sent = match payload {
+                        Ok(payload) => socket.send_to(&payload).await,
+                        Err(err) => Err(err.into()),
+                    };
+                    if let Err(err) = sent {
+                        debug!("pull request to addr:{addr} err:{err}");
+                    }
+                }
+            }
+            _ = purge_interval.tick() => {
+                let purged = crds
+                    .write()
+                    .await
+                    .purge(since_the_epoch_millis(), CRDS_TIMEOUT_MS);
+                if !purged.is_empty() {
+                    events_tx.send(GossipEvent::Purged(purged)).ok();
+                }
+            }
+            payload = demux.recv() => {
+                // the demux stops on a socket error only
+                let payload = payload.ok_or(Error::InputError)??;
+                let Some(from_addr) = payload.addr else {
+                    continue;
+                };
+
+                match payload.deserialize_slice(..) {
+                    Ok(protocol) => {
+                        let entrypoint_key = match &protocol {
+                            Protocol::PullResponse(from_key, _) if from_addr == entrypoint_addr => {
+                                Some(*from_key)
+                            }
+                            _ => None,
+                        };
+
+                        let processed =
+                            process_protocol(protocol, from_addr, &socket, &keypair, &crds, &events_tx)
+                                .await;
+                        if let Err(err) = processed {
+                            debug!("from_addr:{from_addr} err:{err}");
+                        }
+
+                        // learn the cluster shred version from the entrypoint
+                        if let (0, Some(from_key)) = (contact_info.shred_version, entrypoint_key) {
+                            let discovered = crds.read().await.get_shred_version(&from_key);
+                            if let Some(shred_version) = discovered {
+                                info!("shred_version:{shred_version}");
+                                contact_info.shred_version = shred_version;
+                                events_tx.send(GossipEvent::ShredVersion(shred_version)).ok();
+                            }
+                        }
+                    }
+                    Err(err) => debug!("err:{err:?}"),
+                }
+            }
+        }
+    }
+}
+
+// gossip addresses of the nodes in the table, but the local one, the nodes
+// of other clusters are skipped once the shred version is known
+fn gossip_peers(crds: &Crds, contact_info: &LegacyContactInfo) -> Vec<SocketAddr> {
+    crds.pubkeys()
+        .filter(|other| **other != contact_info.id)
+        .filter_map(|other| crds.get_contact_info(other))
+        .filter(|info| {
+            contact_info.shred_version == 0 || info.shred_version == contact_info.shred_version
+        })
+        .map(|info| info.gossip)
+        .filter(|addr| !addr.ip().is_unspecified() && addr.port() != 0)
+        .collect()
+}
+
+async fn process_protocol(
+    protocol: Protocol,
+    from_addr: SocketAddr,
+    socket: &GossipSocket,
+    keypair: &Keypair,
+    crds: &RwLock<Crds>,
+    events_tx: &broadcast::Sender<GossipEvent>,
+) -> Result<()> {
+    let event = match protocol {
+        Protocol::PingMessage(ping) => {
+            if ping.verify() {
+                let payload = create_pong_response(&ping, from_addr, keypair)?;
+                socket.send_to(&payload).await?;
+                GossipEvent::Ping(ping.from)
+            } else {
+                GossipEvent::Unverified(ping.from)
+            }
+        }
+        Protocol::PongMessage(pong) => {
+            if pong.verify() {
+                GossipEvent::Pong(pong.from)
+            } else {
+                GossipEvent::Unverified(pong.from)
+            }
+        }
+        Protocol::PullResponse(_, values) | Protocol::PushMessage(_, values) => {
+            let mut crds = crds.write().await;
+            for value in values {
+                if !value.verify() {
+                    events_tx.send(GossipEvent::Unverified(value.pubkey())).ok();
+                    continue;
+                }
+
+                match crds.insert(value.clone(), since_the_epoch_millis()) {
+                    Ok(CrdsInsertion::Inserted) => {
+                        events_tx.send(GossipEvent::Upserted(Box::new(value))).ok();
+                    }
+                    Ok(_) => {}
+                    Err(err) => debug!("{:?} err:{err}", value.label()),
+                }
+            }
+            return Ok(());
+        }
+        Protocol::PruneMessage(from, prune_data) => {
+            if prune_data.verify() {
+                GossipEvent::Prune(Box::new(prune_data))
+            } else {
+                GossipEvent::Unverified(from)
+            }
+        }
+        Protocol::PullRequest(..) => return Ok(()),
+    };
+
+    events_tx.send(event).ok();
+    Ok(())
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_gossip_proto::{
+        protocol::{CrdsData, Ping},
+        wire::Payload,
+    };
+    use std::net::Ipv4Addr;
+    use tokio::time::{sleep, timeout};
+
+    const TEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+    // the next message received by the socket accepted by the filter
+    async fn recv_protocol(
+        socket: &GossipSocket,
+        accept: impl Fn(&Protocol) -> bool,
+    ) -> (Protocol, SocketAddr) {
+        let recv = async {
+            loop {
+                let payload = socket.recv_from().await.unwrap();
+                let protocol = payload.deserialize_slice(..).unwrap();
+                if accept(&protocol) {
+                    return (protocol, payload.addr.unwrap());
+                }
+            }
+        };
+        timeout(TEST_TIMEOUT, recv).await.unwrap()
+    }
+
+    async fn send_protocol(socket: &GossipSocket, protocol: &Protocol, addr: SocketAddr) {
+        let mut payload = Payload::default();
+        payload.populate_packet(Some(addr), protocol).unwrap();
+        socket.send_to(&payload).await.unwrap();
+    }
+
+    async fn ping_pong(entrypoint: &GossipSocket, keypair: &Keypair, client_addr: SocketAddr) {
+        let request = Protocol::PingMessage(Ping::new([7; 32], keypair).unwrap());
+        send_protocol(entrypoint, &request, client_addr).await;
+
+        let (response, _) = recv_protocol(entrypoint, |protocol| {
+            matches!(protocol, Protocol::PongMessage(_))
+        })
+        .await;
+        assert!(matches!(response, Protocol::PongMessage(pong) if pong.verify()));
+    }
+
+    #[tokio::test]
+    async fn test_client_loopback() {
+        let loopback = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+        let entrypoint = GossipSocket::bind(loopback).await.unwrap();
+        let entrypoint_addr = entrypoint.local_addr().unwrap();
+        let keypair = Keypair::new();
+
+        let socket = GossipSocket::bind(loopback).await.unwrap();
+        let client = GossipClient::spawn(socket, entrypoint_addr, Keypair::new(), 0);
+        let mut events_rx = client.subscribe();
+
+        // the client pulls the entrypoint and answers its pings
+        let (_, client_addr) = recv_protocol(&entrypoint, |protocol| {
+            matches!(protocol, Protocol::PullRequest(..))
+        })
+        .await;
+        ping_pong(&entrypoint, &keypair, client_addr).await;
+
+        // the pulled values are published, a peer on an unreachable ipv6
+        // address is pulled as well
+        let info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            gossip: entrypoint_addr,
+            wallclock: since_the_epoch_millis(),
+            ..LegacyContactInfo::default()
+        };
+        let value = CrdsValue::new_signed(CrdsData::LegacyContactInfo(Box::new(info)), &keypair);
+        let peer_keypair = Keypair::new();
+        let peer_info = LegacyContactInfo {
+            id: peer_keypair.pubkey(),
+            gossip: "[::1]:8001".parse().unwrap(),
+            wallclock: since_the_epoch_millis(),
+            ..LegacyContactInfo::default()
+        };
+        let peer_value = CrdsValue::new_signed(
+            CrdsData::LegacyContactInfo(Box::new(peer_info)),
+            &peer_keypair,
+        );
+        let response = Protocol::PullResponse(keypair.pubkey(), vec![value.clone(), peer_value]);
+        send_protocol(&entrypoint, &response, client_addr).await;
+
+        let mut events = vec![];
+        let upserted = timeout(TEST_TIMEOUT, async {
+            loop {
+                match events_rx.recv().await.unwrap() {
+                    GossipEvent::Upserted(upserted) => return upserted,
+                    event => events.push(event),
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(*upserted, value);
+        assert!(matches!(events[0], GossipEvent::Ping(from) if from == keypair.pubkey()));
+        assert!(client
+            .crds()
+            .read()
+            .await
+            .get_contact_info(&keypair.pubkey())
+            .is_some());
+
+        // the failing pull requests to the peer do not stop the client
+        sleep(Duration::from_millis(PULL_INTERVAL * 3)).await;
+        ping_pong(&entrypoint, &keypair, client_addr).await;
+
+        client.shutdown().await;
+    }
+}
@@ -12,12 +12,18 @@ pub enum ErrorKind {
     #[error("Timeout error")]
     TimeouttError,
 
+    #[error("Signature verification error")]
+    VerifyError,
+
     #[error(transparent)]
     AddrParseError(#[from] std::net::AddrParseError),
 
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
+    #[error(transparent)]
+    JoinError(#[from] tokio::task::JoinError),
+
     #[error(transparent)]
     ProtoError(#[from] solana_gossip_proto::errors::Error),
 }
@@ -4,25 +4,30 @@ use log::info;
 use tokio::time::timeout;
 
 use solana_gossip_proto::{
-    protocol::{CrdsData, LegacyContactInfo, Protocol},
+    crds::Crds,
+    protocol::{CrdsValue, LegacyContactInfo, Protocol, CRDS_FILTER_MAX_BYTES},
     utils::{create_pong_response, create_pull_request, since_the_epoch_millis},
 };
 use solana_sdk::{signature::Keypair, signer::Signer};
 
 use crate::{
-    connection::Connection,
     errors::{Error, Result},
+    socket::PeerStream,
 };
 
 const UDP_TIMEOUT: u64 = 200; // 200msec
 const HANDSHAKE_TIMEOUT: u64 = 5000; // 5sec
 
-pub async fn handshake(conn: &mut Connection) -> Result<Option<Box<LegacyContactInfo>>> {
-    let keypair = Keypair::new();
-    let shred_version: u16 = 0;
-
-    let local_addr = conn.local_addr();
-    let entrypoint_addr = conn.entrypoint_addr();
+/// Pulls the entrypoint until a contact info is received, the verified values
+/// are stored in the given crds table.
+pub async fn handshake(
+    stream: &mut PeerStream,
+    keypair: &Keypair,
+    shred_version: u16,
+    crds: &mut Crds,
+) -> Result<Option<Box<LegacyContactInfo>>> {
+    let local_addr = stream.local_addr()?;
+    let entrypoint_addr = stream.peer_addr();
 
     info!("local_addr:{local_addr:?} entrypoint_addr:{entrypoint_addr:?}");
 
@@ -34,33 +39,48 @@ pub async fn handshake(conn: &mut Connection) -> Result<Option<Box<LegacyContact
         ..LegacyContactInfo::default()
     };
 
-    let payload = create_pull_request(contact_info.clone(), &keypair, entrypoint_addr)?;
+    let mut filters = crds.filters(CRDS_FILTER_MAX_BYTES);
+    let filter = filters.pop().unwrap_or_default();
+    let payload = create_pull_request(contact_info.clone(), filter, keypair, entrypoint_addr)?;
 
-    conn.send(payload).await?;
+    stream.send(payload).await?;
 
-    if let Some(payload) = conn.receive().await? {
+    if let Some(payload) = stream.receive().await {
         if let Ok(Protocol::PingMessage(ping)) = payload.deserialize_slice(..) {
-            let pong_payload = create_pong_response(&ping, entrypoint_addr, &keypair)?;
+            if !ping.verify() {
+                return Err(Error::VerifyError);
+            }
 
-            conn.send(pong_payload).await?;
+            let pong_payload = create_pong_response(&ping, entrypoint_addr, keypair)?;
+
+            stream.send(pong_payload).await?;
 
             info!("pong has been sended.");
 
             let now = since_the_epoch_millis();
 
             loop {
-                if let Ok(Ok(Some(payload))) =
-                    timeout(Duration::from_millis(UDP_TIMEOUT), conn.receive()).await
+                if let Ok(Some(payload)) =
+                    timeout(Duration::from_millis(UDP_TIMEOUT), stream.receive()).await
                 {
                     let after_pong_protocol = payload.deserialize_slice(..);
 
-                    if let Ok(Protocol::PullResponse(_, values)) = after_pong_protocol {
-                        for value in values {
+                    if let Ok(Protocol::PullResponse(from_key, values)) = after_pong_protocol {
+                        let mut pubkeys = vec![from_key];
+
+                        for value in values.into_iter().filter(CrdsValue::verify) {
                             info!("message {value} has been received.");
 
-                            if let CrdsData::LegacyContactInfo(info) = value.data {
-                                return Ok(Some(info));
-                            }
+                            pubkeys.push(value.pubkey());
+                            crds.insert(value, since_the_epoch_millis())?;
+                        }
+
+                        // prefer the contact info of the entrypoint itself
+                        if let Some(info) = pubkeys
+                            .iter()
+                            .find_map(|pubkey| crds.get_contact_info(pubkey))
+                        {
+                            return Ok(Some(Box::new(info)));
                         }
                     }
                 }
@@ -69,9 +89,14 @@ pub async fn handshake(conn: &mut Connection) -> Result<Option<Box<LegacyContact
                     return Err(Error::TimeouttError);
                 }
 
-                let payload = create_pull_request(contact_info.clone(), &keypair, entrypoint_addr)?;
+                if filters.is_empty() {
+                    filters = crds.filters(CRDS_FILTER_MAX_BYTES);
+                }
+                let filter = filters.pop().unwrap_or_default();
+                let payload =
+                    create_pull_request(contact_info.clone(), filter, keypair, entrypoint_addr)?;
 
-                conn.send(payload).await?;
+                stream.send(payload).await?;
             }
         }
     }
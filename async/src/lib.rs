@@ -1,6 +1,7 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::must_use_candidate)]
-pub mod connection;
+pub mod client;
 pub mod errors;
 pub mod handshake;
+pub mod socket;
@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::{Arc, Mutex},
+};
+
+use log::debug;
+use tokio::{
+    net::UdpSocket,
+    sync::mpsc::{self, error::TrySendError},
+    task::JoinHandle,
+};
+
+use solana_gossip_proto::wire::{Payload, PACKET_DATA_SIZE};
+
+use crate::errors::{Error, Result};
+
+const STREAM_CAPACITY: usize = 256;
+
+/// A gossip socket exchanging payloads with any peer.
+#[derive(Debug)]
+pub struct GossipSocket {
+    socket: UdpSocket,
+}
+
+impl GossipSocket {
+    pub async fn bind(addr: SocketAddr) -> Result<Self> {
+        let socket = UdpSocket::bind(addr).await?;
+        Ok(Self { socket })
+    }
+
+    /// Binds an ephemeral port on all the interfaces.
+    pub async fn bind_any() -> Result<Self> {
+        Self::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0)).await
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// Sends the payload to its `addr`.
+    pub async fn send_to(&self, payload: &Payload) -> Result<()> {
+        let (Some(addr), Some(buf)) = (payload.addr, payload.data(..)) else {
+            return Err(Error::InputError);
+        };
+
+        self.socket.send_to(buf, addr).await?;
+        Ok(())
+    }
+
+    /// Receives the next payload, its `addr` is the one of the sender.
+    pub async fn recv_from(&self) -> Result<Payload> {
+        let mut buf = [0; PACKET_DATA_SIZE];
+
+        let (len, addr) = self.socket.recv_from(&mut buf).await?;
+
+        Ok(Payload {
+            len,
+            buf,
+            addr: Some(addr),
+        })
+    }
+}
+
+type Routes = Arc<Mutex<HashMap<SocketAddr, mpsc::Sender<Payload>>>>;
+
+/// Reads the socket on its own task and routes every payload to the stream
+/// registered for the sender address, the others are returned by `recv`.
+pub struct Demux {
+    socket: Arc<GossipSocket>,
+    routes: Routes,
+    rx: mpsc::Receiver<Result<Payload>>,
+    task: JoinHandle<()>,
+}
+
+impl Demux {
+    pub fn spawn(socket: Arc<GossipSocket>) -> Self {
+        let routes: Routes = Arc::default();
+        let (tx, rx) = mpsc::channel(STREAM_CAPACITY);
+
+        let task = tokio::spawn(route(socket.clone(), routes.clone(), tx));
+
+        Self {
+            socket,
+            routes,
+            rx,
+            task,
+        }
+    }
+
+    pub fn socket(&self) -> Arc<GossipSocket> {
+        self.socket.clone()
+    }
+
+    /// Payloads received from the peers without a registered stream, a socket
+    /// error is returned once and then the demux stops.
+    pub async fn recv(&mut self) -> Option<Result<Payload>> {
+        self.rx.recv().await
+    }
+
+    /// Registers the stream of the given peer, replacing any previous one.
+    pub fn stream(&self, peer_addr: SocketAddr) -> PeerStream {
+        let (tx, rx) = mpsc::channel(STREAM_CAPACITY);
+
+        if let Ok(mut routes) = self.routes.lock() {
+            routes.insert(peer_addr, tx);
+        }
+
+        PeerStream {
+            socket: self.socket.clone(),
+            peer_addr,
+            rx,
+        }
+    }
+}
+
+impl Drop for Demux {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn route(socket: Arc<GossipSocket>, routes: Routes, tx: mpsc::Sender<Result<Payload>>) {
+    loop {
+        let payload = match socket.recv_from().await {
+            Ok(payload) => payload,
+            Err(err) => {
+                tx.send(Err(err)).await.ok();
+                return;
+            }
+        };
+
+        let peer_tx = payload.addr.and_then(|addr| {
+            let routes = routes.lock().ok()?;
+            routes.get(&addr).cloned()
+        });
+
+        let payload = match peer_tx {
+            Some(peer_tx) => match peer_tx.try_send(payload) {
+                Ok(()) => continue,
+                Err(TrySendError::Full(_)) => {
+                    debug!("stream full, payload dropped");
+                    continue;
+                }
+                // the stream has been dropped, fall back to the default route
+                Err(TrySendError::Closed(payload)) => {
+                    if let (Some(addr), Ok(mut routes)) = (payload.addr, routes.lock()) {
+                        routes.remove(&addr);
+                    }
+                    payload
+                }
+            },
+            None => payload,
+        };
+
+        if tx.send(Ok(payload)).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// The payloads exchanged with a single peer, e.g. the entrypoint during the
+/// `handshake`.
+pub struct PeerStream {
+    socket: Arc<GossipSocket>,
+    peer_addr: SocketAddr,
+    rx: mpsc::Receiver<Payload>,
+}
+
+impl PeerStream {
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer_addr
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub async fn send(&self, mut payload: Payload) -> Result<()> {
+        payload.addr = Some(self.peer_addr);
+        self.socket.send_to(&payload).await
+    }
+
+    pub async fn receive(&mut self) -> Option<Payload> {
+        self.rx.recv().await
+    }
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    const TEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+    async fn bind_loopback() -> GossipSocket {
+        GossipSocket::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))
+            .await
+            .unwrap()
+    }
+
+    fn new_payload(data: &[u8], addr: Option<SocketAddr>) -> Payload {
+        let mut payload = Payload {
+            len: data.len(),
+            addr,
+            ..Payload::default()
+        };
+        payload.buf[..data.len()].copy_from_slice(data);
+        payload
+    }
+
+    #[tokio::test]
+    async fn test_demux() {
+        let socket = Arc::new(bind_loopback().await);
+        let local_addr = socket.local_addr().unwrap();
+        let mut demux = Demux::spawn(socket);
+
+        let peer = bind_loopback().await;
+        let peer_addr = peer.local_addr().unwrap();
+        let other = bind_loopback().await;
+        let other_addr = other.local_addr().unwrap();
+
+        let mut stream = demux.stream(peer_addr);
+        assert_eq!(stream.peer_addr(), peer_addr);
+
+        // the registered peer goes to its stream, the others to recv
+        peer.send_to(&new_payload(&[1], Some(local_addr)))
+            .await
+            .unwrap();
+        other
+            .send_to(&new_payload(&[2], Some(local_addr)))
+            .await
+            .unwrap();
+
+        let payload = timeout(TEST_TIMEOUT, stream.receive()).await.unwrap();
+        assert_eq!(payload.unwrap().data(..), Some(&[1][..]));
+        let payload = timeout(TEST_TIMEOUT, demux.recv()).await.unwrap();
+        let payload = payload.unwrap().unwrap();
+        assert_eq!(payload.data(..), Some(&[2][..]));
+        assert_eq!(payload.addr, Some(other_addr));
+
+        // the stream sends to its peer
+        stream.send(new_payload(&[3], None)).await.unwrap();
+        let payload = timeout(TEST_TIMEOUT, peer.recv_from()).await.unwrap();
+        let payload = payload.unwrap();
+        assert_eq!(payload.data(..), Some(&[3][..]));
+        assert_eq!(payload.addr, Some(local_addr));
+
+        // once the stream is dropped the peer goes to recv
+        drop(stream);
+        peer.send_to(&new_payload(&[4], Some(local_addr)))
+            .await
+            .unwrap();
+        let payload = timeout(TEST_TIMEOUT, demux.recv()).await.unwrap();
+        let payload = payload.unwrap().unwrap();
+        assert_eq!(payload.data(..), Some(&[4][..]));
+        assert_eq!(payload.addr, Some(peer_addr));
+    }
+
+    #[tokio::test]
+    async fn test_send_to_without_addr() {
+        let socket = bind_loopback().await;
+
+        let sent = socket.send_to(&new_payload(&[1], None)).await;
+        assert!(matches!(sent, Err(Error::InputError)));
+    }
+}
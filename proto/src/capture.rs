@@ -0,0 +1,181 @@
+use std::{
+    io::{self, Read, Write},
+    net::SocketAddr,
+};
+
+use bincode::Options;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    errors::{Error, Result},
+    wire::{Payload, PACKET_DATA_SIZE},
+};
+
+/// First bytes of a capture file, followed by the bincode encoded records.
+pub const CAPTURE_MAGIC: &[u8; 8] = b"SGTCAP01";
+
+// a record is a little larger than its packet
+const RECORD_SIZE_LIMIT: u64 = PACKET_DATA_SIZE as u64 + 64;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Received,
+    Sent,
+}
+
+/// A packet received from or sent to a peer.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CaptureRecord {
+    /// local time in millis
+    pub timestamp: u64,
+    pub direction: Direction,
+    pub addr: Option<SocketAddr>,
+    pub data: Vec<u8>,
+}
+
+impl CaptureRecord {
+    pub fn new(timestamp: u64, direction: Direction, payload: &Payload) -> Self {
+        CaptureRecord {
+            timestamp,
+            direction,
+            addr: payload.addr,
+            data: payload.data(..).unwrap_or_default().to_vec(),
+        }
+    }
+
+    /// The recorded packet, `None` if larger than a gossip packet.
+    pub fn payload(&self) -> Option<Payload> {
+        let mut payload = Payload {
+            len: self.data.len(),
+            addr: self.addr,
+            ..Payload::default()
+        };
+        payload
+            .buf
+            .get_mut(..self.data.len())?
+            .copy_from_slice(&self.data);
+
+        Some(payload)
+    }
+}
+
+fn options() -> impl Options {
+    bincode::options()
+        .with_limit(RECORD_SIZE_LIMIT)
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+}
+
+/// Writes the records of a capture file.
+pub struct CaptureWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CaptureWriter<W> {
+    pub fn new(mut writer: W) -> Result<Self> {
+        writer.write_all(CAPTURE_MAGIC)?;
+        Ok(CaptureWriter { writer })
+    }
+
+    pub fn write(&mut self, record: &CaptureRecord) -> Result<()> {
+        options()
+            .serialize_into(&mut self.writer, record)
+            .map_err(Into::into)
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(Into::into)
+    }
+}
+
+/// Reads the records of a capture file, a record truncated by an interrupted
+/// recording ends the capture.
+pub struct CaptureReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0; CAPTURE_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != CAPTURE_MAGIC {
+            return Err(Error::CaptureError);
+        }
+
+        Ok(CaptureReader { reader })
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<CaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match options().deserialize_from(&mut self.reader) {
+            Ok(record) => Some(Ok(record)),
+            Err(err) => match *err {
+                bincode::ErrorKind::Io(err) if err.kind() == io::ErrorKind::UnexpectedEof => None,
+                _ => Some(Err(err.into())),
+            },
+        }
+    }
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn new_record(direction: Direction, data: &[u8]) -> CaptureRecord {
+        CaptureRecord {
+            timestamp: 1_700_000_000_000,
+            direction,
+            addr: Some("10.0.0.1:8001".parse().unwrap()),
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_capture_round_trip() {
+        let records = vec![
+            new_record(Direction::Received, &[1, 2, 3]),
+            new_record(Direction::Sent, &[4; PACKET_DATA_SIZE]),
+        ];
+
+        let mut writer = CaptureWriter::new(Vec::new()).unwrap();
+        for record in &records {
+            writer.write(record).unwrap();
+        }
+        let mut bytes = writer.writer;
+        assert!(bytes.starts_with(CAPTURE_MAGIC));
+
+        let reader = CaptureReader::new(bytes.as_slice()).unwrap();
+        let read = reader.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(read, records);
+
+        let payload = read[1].payload().unwrap();
+        assert_eq!(payload.len, PACKET_DATA_SIZE);
+        assert_eq!(payload.addr, records[1].addr);
+        assert_eq!(
+            CaptureRecord::new(0, Direction::Sent, &payload).data,
+            records[1].data
+        );
+
+        // the truncated last record is dropped
+        bytes.truncate(bytes.len() - 1);
+        let reader = CaptureReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.count(), 1);
+    }
+
+    #[test]
+    fn test_capture_invalid() {
+        assert!(matches!(
+            CaptureReader::new(&b"NOTACAPTURE"[..]),
+            Err(Error::CaptureError)
+        ));
+        assert!(CaptureReader::new(&b"SG"[..]).is_err());
+
+        let record = new_record(Direction::Received, &[0; PACKET_DATA_SIZE + 1]);
+        assert!(record.payload().is_none());
+    }
+}
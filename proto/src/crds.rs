@@ -0,0 +1,406 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use bincode::serialize;
+use solana_sdk::{
+    hash::{hash, Hash},
+    pubkey::Pubkey,
+};
+
+use crate::errors::Result;
+use crate::protocol::{
+    CrdsData, CrdsFilter, CrdsFilterSet, CrdsValue, CrdsValueLabel, LegacyContactInfo, NodeVersion,
+};
+
+/// Values not updated within this time are dropped by `Crds::purge`.
+pub const CRDS_TIMEOUT_MS: u64 = 60_000;
+
+/// Outcome of `Crds::insert`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CrdsInsertion {
+    /// the value is new or overrides the one stored for the same label
+    Inserted,
+    /// a value with a later wallclock or a greater hash is already stored
+    Outdated,
+    /// the very same value is already stored
+    Duplicate,
+}
+
+/// A value stored in the table along with its local metadata.
+#[derive(Clone, Debug)]
+pub struct VersionedCrdsValue {
+    /// insertion order of the value in the table
+    pub ordinal: u64,
+    pub value: CrdsValue,
+    /// local time, in millis, when the value has been inserted
+    pub local_timestamp: u64,
+    pub value_hash: Hash,
+}
+
+/// The local copy of the cluster replicated data store, at most one value
+/// for each `CrdsValueLabel` is kept.
+#[derive(Debug, Default)]
+pub struct Crds {
+    table: HashMap<CrdsValueLabel, VersionedCrdsValue>,
+    records: HashMap<Pubkey, HashSet<CrdsValueLabel>>,
+    num_inserts: u64,
+}
+
+// same ordering used by the validators, the newest wallclock wins and ties are
+// broken by the hash of the serialized values.
+fn overrides(value: &CrdsValue, value_hash: &Hash, other: &VersionedCrdsValue) -> bool {
+    match value.wallclock().cmp(&other.value.wallclock()) {
+        Ordering::Less => false,
+        Ordering::Greater => true,
+        Ordering::Equal => other.value_hash < *value_hash,
+    }
+}
+
+impl Crds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, value: CrdsValue, now: u64) -> Result<CrdsInsertion> {
+        let label = value.label();
+        let value_hash = hash(&serialize(&value)?);
+
+        if let Some(other) = self.table.get(&label) {
+            if !overrides(&value, &value_hash, other) {
+                if other.value_hash == value_hash {
+                    return Ok(CrdsInsertion::Duplicate);
+                }
+                return Ok(CrdsInsertion::Outdated);
+            }
+        }
+
+        self.records
+            .entry(label.pubkey())
+            .or_default()
+            .insert(label);
+        self.table.insert(
+            label,
+            VersionedCrdsValue {
+                ordinal: self.num_inserts,
+                value,
+                local_timestamp: now,
+                value_hash,
+            },
+        );
+        self.num_inserts += 1;
+
+        Ok(CrdsInsertion::Inserted)
+    }
+
+    pub fn get(&self, label: &CrdsValueLabel) -> Option<&VersionedCrdsValue> {
+        self.table.get(label)
+    }
+
+    /// All the values originated by the given pubkey.
+    pub fn get_records<'a>(
+        &'a self,
+        pubkey: &Pubkey,
+    ) -> impl Iterator<Item = &'a VersionedCrdsValue> + 'a {
+        self.records
+            .get(pubkey)
+            .into_iter()
+            .flatten()
+            .filter_map(|label| self.table.get(label))
+    }
+
+    /// The most recent contact info of the node, the new `ContactInfo` is
+    /// converted to the legacy one.
+    pub fn get_contact_info(&self, pubkey: &Pubkey) -> Option<LegacyContactInfo> {
+        let legacy = self.get(&CrdsValueLabel::LegacyContactInfo(*pubkey));
+        let info = self.get(&CrdsValueLabel::ContactInfo(*pubkey));
+
+        [legacy, info]
+            .into_iter()
+            .flatten()
+            .max_by_key(|entry| entry.value.wallclock())
+            .and_then(|entry| match &entry.value.data {
+                CrdsData::LegacyContactInfo(info) => Some(info.as_ref().clone()),
+                CrdsData::ContactInfo(info) => Some(LegacyContactInfo::from(info.as_ref())),
+                _ => None,
+            })
+    }
+
+    /// The most recently advertised client version of the node.
+    pub fn get_version(&self, pubkey: &Pubkey) -> Option<NodeVersion> {
+        let labels = [
+            CrdsValueLabel::LegacyVersion(*pubkey),
+            CrdsValueLabel::Version(*pubkey),
+            CrdsValueLabel::ContactInfo(*pubkey),
+        ];
+
+        labels
+            .iter()
+            .filter_map(|label| self.get(label))
+            .max_by_key(|entry| entry.value.wallclock())
+            .and_then(|entry| match &entry.value.data {
+                CrdsData::LegacyVersion(version) => Some(NodeVersion::from(&version.version)),
+                CrdsData::Version(version) => Some(NodeVersion::from(&version.version)),
+                CrdsData::ContactInfo(info) => Some(NodeVersion::from(&info.version)),
+                _ => None,
+            })
+    }
+
+    /// The shred version advertised by the node, None if not known yet.
+    pub fn get_shred_version(&self, pubkey: &Pubkey) -> Option<u16> {
+        self.get_contact_info(pubkey)
+            .map(|info| info.shred_version)
+            .filter(|shred_version| *shred_version != 0)
+    }
+
+    /// Pubkeys of the nodes with at least one value in the table.
+    pub fn pubkeys(&self) -> impl Iterator<Item = &Pubkey> {
+        self.records.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &VersionedCrdsValue> {
+        self.table.values()
+    }
+
+    /// Pull request filters of the values already held, one for each mask.
+    pub fn filters(&self, max_bytes: usize) -> Vec<CrdsFilter> {
+        let mut filter_set = CrdsFilterSet::new(self.table.len(), max_bytes);
+        for entry in self.table.values() {
+            filter_set.add(&entry.value_hash);
+        }
+        filter_set.into()
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    pub fn remove(&mut self, label: &CrdsValueLabel) -> Option<VersionedCrdsValue> {
+        let entry = self.table.remove(label)?;
+
+        let pubkey = label.pubkey();
+        if let Some(labels) = self.records.get_mut(&pubkey) {
+            labels.remove(label);
+            if labels.is_empty() {
+                self.records.remove(&pubkey);
+            }
+        }
+
+        Some(entry)
+    }
+
+    /// Drops the values inserted more than `timeout` millis before `now` and
+    /// returns their labels.
+    pub fn purge(&mut self, now: u64, timeout: u64) -> Vec<CrdsValueLabel> {
+        let labels = self
+            .table
+            .iter()
+            .filter(|(_, entry)| entry.local_timestamp.saturating_add(timeout) < now)
+            .map(|(label, _)| *label)
+            .collect::<Vec<_>>();
+
+        for label in &labels {
+            self.remove(label);
+        }
+
+        labels
+    }
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+    use solana_sdk::signature::Keypair;
+    use solana_sdk::signer::Signer;
+
+    use super::*;
+    use crate::protocol::{ContactInfo, LegacyVersion2, Version};
+
+    fn create_contact_info(keypair: &Keypair, wallclock: u64, shred_version: u16) -> CrdsValue {
+        let info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            wallclock,
+            shred_version,
+            ..LegacyContactInfo::default()
+        };
+        CrdsValue::new_signed(CrdsData::LegacyContactInfo(Box::new(info)), keypair)
+    }
+
+    fn create_version(keypair: &Keypair, wallclock: u64) -> CrdsValue {
+        let version = Version {
+            from: keypair.pubkey(),
+            wallclock,
+            version: LegacyVersion2 {
+                major: 1,
+                minor: 18,
+                patch: 0,
+                commit: None,
+                feature_set: 0,
+            },
+        };
+        CrdsValue::new_signed(CrdsData::Version(version), keypair)
+    }
+
+    #[test]
+    fn test_insert_wallclock_ordering() {
+        let keypair = Keypair::new();
+        let mut crds = Crds::new();
+
+        let value = create_contact_info(&keypair, 100, 0);
+        assert_eq!(
+            crds.insert(value.clone(), 1).unwrap(),
+            CrdsInsertion::Inserted
+        );
+        assert_eq!(crds.insert(value, 2).unwrap(), CrdsInsertion::Duplicate);
+
+        let older = create_contact_info(&keypair, 99, 1);
+        assert_eq!(crds.insert(older, 3).unwrap(), CrdsInsertion::Outdated);
+
+        let newer = create_contact_info(&keypair, 101, 2);
+        assert_eq!(
+            crds.insert(newer.clone(), 4).unwrap(),
+            CrdsInsertion::Inserted
+        );
+
+        assert_eq!(crds.len(), 1);
+        let entry = crds.get(&newer.label()).unwrap();
+        assert_eq!(entry.value, newer);
+        assert_eq!(entry.ordinal, 1);
+        assert_eq!(entry.local_timestamp, 4);
+    }
+
+    #[test]
+    fn test_insert_same_wallclock_by_hash() {
+        let keypair = Keypair::new();
+
+        let value_a = create_contact_info(&keypair, 100, 1);
+        let value_b = create_contact_info(&keypair, 100, 2);
+        let (low, high) =
+            if hash(&serialize(&value_a).unwrap()) < hash(&serialize(&value_b).unwrap()) {
+                (value_a, value_b)
+            } else {
+                (value_b, value_a)
+            };
+
+        let mut crds = Crds::new();
+        assert_eq!(
+            crds.insert(low.clone(), 0).unwrap(),
+            CrdsInsertion::Inserted
+        );
+        assert_eq!(
+            crds.insert(high.clone(), 0).unwrap(),
+            CrdsInsertion::Inserted
+        );
+        assert_eq!(crds.insert(low, 0).unwrap(), CrdsInsertion::Outdated);
+        assert_eq!(crds.get(&high.label()).unwrap().value, high);
+    }
+
+    #[test]
+    fn test_records_and_contact_info() {
+        let keypair = Keypair::new();
+        let other = Keypair::new();
+        let mut crds = Crds::new();
+
+        crds.insert(create_contact_info(&keypair, 100, 0), 0)
+            .unwrap();
+        crds.insert(create_version(&keypair, 100), 0).unwrap();
+        crds.insert(create_version(&other, 100), 0).unwrap();
+
+        assert_eq!(crds.len(), 3);
+        assert_eq!(crds.pubkeys().count(), 2);
+        assert_eq!(crds.get_records(&keypair.pubkey()).count(), 2);
+        assert_eq!(crds.get_records(&other.pubkey()).count(), 1);
+        assert_eq!(crds.get_records(&Pubkey::new_unique()).count(), 0);
+
+        assert_eq!(
+            crds.get_contact_info(&keypair.pubkey()).unwrap().wallclock,
+            100
+        );
+        assert!(crds.get_contact_info(&other.pubkey()).is_none());
+        // zero means the shred version is unknown
+        assert_eq!(crds.get_shred_version(&keypair.pubkey()), None);
+        assert_eq!(
+            crds.get_version(&other.pubkey()).unwrap().to_string(),
+            "1.18.0"
+        );
+        assert!(crds.get_version(&Pubkey::new_unique()).is_none());
+
+        // the newer ContactInfo takes over the legacy one
+        let mut info = ContactInfo::new(keypair.pubkey(), 200, 7);
+        info.set_socket(
+            crate::protocol::SOCKET_TAG_GOSSIP,
+            "10.20.30.40:8001".parse().unwrap(),
+        );
+        crds.insert(
+            CrdsValue::new_signed(CrdsData::ContactInfo(Box::new(info)), &keypair),
+            0,
+        )
+        .unwrap();
+
+        let legacy = crds.get_contact_info(&keypair.pubkey()).unwrap();
+        assert_eq!(legacy.wallclock, 200);
+        assert_eq!(legacy.shred_version, 7);
+        assert_eq!(legacy.gossip, "10.20.30.40:8001".parse().unwrap());
+        assert_eq!(crds.get_shred_version(&keypair.pubkey()), Some(7));
+        // and so its version
+        assert_eq!(crds.get_version(&keypair.pubkey()).unwrap().commit, Some(0));
+    }
+
+    #[test]
+    fn test_purge() {
+        let keypair = Keypair::new();
+        let other = Keypair::new();
+        let mut crds = Crds::new();
+
+        crds.insert(create_contact_info(&keypair, 100, 0), 1_000)
+            .unwrap();
+        crds.insert(create_version(&keypair, 100), 5_000).unwrap();
+        crds.insert(create_version(&other, 100), 1_000).unwrap();
+
+        assert!(crds.purge(5_000, 4_000).is_empty());
+
+        let mut purged = crds.purge(5_001, 4_000);
+        purged.sort_by_key(|label| label.pubkey() == keypair.pubkey());
+        assert_eq!(
+            purged,
+            vec![
+                CrdsValueLabel::Version(other.pubkey()),
+                CrdsValueLabel::LegacyContactInfo(keypair.pubkey()),
+            ]
+        );
+
+        assert_eq!(crds.len(), 1);
+        assert_eq!(crds.pubkeys().collect::<Vec<_>>(), vec![&keypair.pubkey()]);
+        assert!(crds.get_contact_info(&keypair.pubkey()).is_none());
+
+        assert!(crds
+            .remove(&CrdsValueLabel::Version(keypair.pubkey()))
+            .is_some());
+        assert!(crds.is_empty());
+        assert_eq!(crds.pubkeys().count(), 0);
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut crds = Crds::new();
+        for wallclock in 0..2_000 {
+            crds.insert(create_contact_info(&Keypair::new(), wallclock, 0), 0)
+                .unwrap();
+        }
+
+        let filters = crds.filters(crate::protocol::CRDS_FILTER_MAX_BYTES);
+        assert_eq!(filters.len(), 2);
+
+        // none of the held values is requested again
+        for entry in crds.values() {
+            assert!(filters
+                .iter()
+                .all(|filter| filter.contains(&entry.value_hash)));
+        }
+    }
+}
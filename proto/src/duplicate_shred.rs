@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use bincode::serialize;
+use serde_derive::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::errors::{Error, Result};
+use crate::protocol::{DuplicateShred, ShredType, Slot};
+
+/// max number of incomplete proofs, the oldest one is dropped beyond it
+pub const MAX_PENDING_PROOFS: usize = 64;
+
+/// The two conflicting shreds carried, chunk by chunk, by the `DuplicateShred` values.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DuplicateSlotProof {
+    pub shred1: Vec<u8>,
+    pub shred2: Vec<u8>,
+}
+
+/// Splits a proof into the `DuplicateShred` chunks as they travel over gossip.
+pub fn split_proof(
+    from: Pubkey,
+    wallclock: u64,
+    slot: Slot,
+    shred_index: u32,
+    shred_type: ShredType,
+    proof: &DuplicateSlotProof,
+    max_chunk_size: usize,
+) -> Result<Vec<DuplicateShred>> {
+    if max_chunk_size == 0 {
+        return Err(Error::DuplicateShredError);
+    }
+
+    let data = serialize(proof)?;
+    let chunks = data.chunks(max_chunk_size).collect::<Vec<_>>();
+    let num_chunks = u8::try_from(chunks.len()).map_err(|_| Error::DuplicateShredError)?;
+
+    Ok(chunks
+        .into_iter()
+        .zip(0..num_chunks)
+        .map(|(chunk, chunk_index)| DuplicateShred {
+            from,
+            wallclock,
+            slot,
+            shred_index,
+            shred_type,
+            num_chunks,
+            chunk_index,
+            chunk: chunk.to_vec(),
+        })
+        .collect())
+}
+
+/// Collects the `DuplicateShred` chunks keyed by (from, slot) and rebuilds the
+/// `DuplicateSlotProof` once all the chunks have been received.
+#[derive(Debug, Default)]
+pub struct DuplicateShredReassembler {
+    pending: HashMap<(Pubkey, Slot), PendingProof>,
+    inserted: u64,
+}
+
+#[derive(Debug)]
+struct PendingProof {
+    // the insertion order, the oldest proof is evicted first
+    inserted: u64,
+    chunks: Vec<Option<Vec<u8>>>,
+}
+
+impl DuplicateShredReassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the proof when the given chunk completes it, None otherwise.
+    pub fn insert(&mut self, shred: &DuplicateShred) -> Result<Option<DuplicateSlotProof>> {
+        let num_chunks = usize::from(shred.num_chunks);
+        let chunk_index = usize::from(shred.chunk_index);
+
+        if chunk_index >= num_chunks {
+            return Err(Error::DuplicateShredError);
+        }
+
+        let key = (shred.from, shred.slot);
+        if !self.pending.contains_key(&key) {
+            self.evict();
+        }
+
+        self.inserted += 1;
+        let inserted = self.inserted;
+        let chunks = &mut self
+            .pending
+            .entry(key)
+            .or_insert_with(|| PendingProof {
+                inserted,
+                chunks: vec![None; num_chunks],
+            })
+            .chunks;
+
+        // a different number of chunks means a new proof for the same slot
+        if chunks.len() != num_chunks {
+            *chunks = vec![None; num_chunks];
+        }
+
+        chunks[chunk_index] = Some(shred.chunk.clone());
+
+        if chunks.iter().any(Option::is_none) {
+            return Ok(None);
+        }
+
+        let data = self
+            .pending
+            .remove(&key)
+            .map(|pending| pending.chunks)
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect::<Vec<u8>>();
+
+        let proof = bincode::deserialize(&data)?;
+        Ok(Some(proof))
+    }
+
+    // makes room for a new proof dropping the one started first, the peers
+    // are free to start proofs they never complete
+    fn evict(&mut self) {
+        if self.pending.len() < MAX_PENDING_PROOFS {
+            return;
+        }
+        let oldest = self
+            .pending
+            .iter()
+            .min_by_key(|(_, pending)| pending.inserted)
+            .map(|(key, _)| *key);
+        if let Some(key) = oldest {
+            self.pending.remove(&key);
+        }
+    }
+
+    /// Number of proofs still waiting for missing chunks.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_proof() -> DuplicateSlotProof {
+        DuplicateSlotProof {
+            shred1: (0..200_u8).collect(),
+            shred2: (0..200_u8).map(|i| i.wrapping_mul(7)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_reassemble_out_of_order() {
+        let from = Pubkey::new_unique();
+        let proof = create_proof();
+
+        let chunks = split_proof(from, 1_000, 42, 7, ShredType::Data, &proof, 64).unwrap();
+        assert_eq!(chunks.len(), 7);
+        assert!(chunks.iter().all(|chunk| chunk.num_chunks == 7));
+
+        let mut reassembler = DuplicateShredReassembler::new();
+
+        for index in [3, 6, 0, 5, 1, 4] {
+            assert_eq!(reassembler.insert(&chunks[index]).unwrap(), None);
+        }
+        assert_eq!(reassembler.len(), 1);
+
+        // a duplicated chunk does not complete the proof
+        assert_eq!(reassembler.insert(&chunks[4]).unwrap(), None);
+
+        assert_eq!(reassembler.insert(&chunks[2]).unwrap(), Some(proof));
+        assert!(reassembler.is_empty());
+    }
+
+    #[test]
+    fn test_reassemble_interleaved_slots() {
+        let from = Pubkey::new_unique();
+        let proof = create_proof();
+
+        let chunks_a = split_proof(from, 1_000, 42, 7, ShredType::Data, &proof, 256).unwrap();
+        let chunks_b = split_proof(from, 1_000, 43, 9, ShredType::Code, &proof, 256).unwrap();
+        assert_eq!(chunks_a.len(), 2);
+
+        let mut reassembler = DuplicateShredReassembler::new();
+
+        assert_eq!(reassembler.insert(&chunks_b[1]).unwrap(), None);
+        assert_eq!(reassembler.insert(&chunks_a[1]).unwrap(), None);
+        assert_eq!(reassembler.len(), 2);
+
+        assert_eq!(
+            reassembler.insert(&chunks_a[0]).unwrap(),
+            Some(proof.clone())
+        );
+        assert_eq!(reassembler.insert(&chunks_b[0]).unwrap(), Some(proof));
+        assert!(reassembler.is_empty());
+    }
+
+    #[test]
+    fn test_reassemble_eviction() {
+        let from = Pubkey::new_unique();
+        let proof = create_proof();
+
+        let mut reassembler = DuplicateShredReassembler::new();
+        let mut first_chunks = vec![];
+        for slot in 0..=MAX_PENDING_PROOFS as u64 {
+            let chunks = split_proof(from, 0, slot, 0, ShredType::Data, &proof, 256).unwrap();
+            assert_eq!(reassembler.insert(&chunks[0]).unwrap(), None);
+            first_chunks.push(chunks);
+        }
+        assert_eq!(reassembler.len(), MAX_PENDING_PROOFS);
+
+        // the proof started first has been dropped, the others still complete
+        assert_eq!(
+            reassembler.insert(&first_chunks[2][1]).unwrap(),
+            Some(proof)
+        );
+        assert_eq!(reassembler.insert(&first_chunks[0][1]).unwrap(), None);
+        assert_eq!(reassembler.len(), MAX_PENDING_PROOFS);
+    }
+
+    #[test]
+    fn test_reassemble_invalid_chunk_index() {
+        let proof = create_proof();
+        let mut chunks =
+            split_proof(Pubkey::new_unique(), 0, 1, 0, ShredType::Data, &proof, 512).unwrap();
+        chunks[0].chunk_index = 1;
+
+        let mut reassembler = DuplicateShredReassembler::new();
+        assert!(reassembler.insert(&chunks[0]).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_shred_crds_value() {
+        use crate::protocol::{CrdsData, CrdsValue};
+        use solana_sdk::signature::Keypair;
+
+        let proof = create_proof();
+        let chunks =
+            split_proof(Pubkey::new_unique(), 0, 1, 0, ShredType::Code, &proof, 512).unwrap();
+
+        let crds_value = CrdsValue::new_signed(
+            CrdsData::DuplicateShred(0, chunks[0].clone()),
+            &Keypair::new(),
+        );
+        let bytes = serialize(&crds_value).unwrap();
+
+        // the shred type is serialized as a single byte
+        let shred_type_offset = 64 + 4 + 2 + 32 + 8 + 8 + 4;
+        assert_eq!(bytes[shred_type_offset], 0b0101_1010);
+
+        let decoded: CrdsValue = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, crds_value);
+    }
+}
@@ -13,6 +13,15 @@ pub enum ErrorKind {
     #[error("Encode error")]
     EncodeError,
 
+    #[error("Invalid duplicate shred chunk")]
+    DuplicateShredError,
+
+    #[error("Invalid capture file")]
+    CaptureError,
+
+    #[error("Keypair error: {0}")]
+    KeypairError(String),
+
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
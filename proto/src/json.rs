@@ -0,0 +1,768 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    net::{IpAddr, SocketAddr},
+};
+
+use bv::{BitVec, Bits};
+use serde_derive::Serialize;
+use solana_sdk::{clock::UnixTimestamp, hash::Hash};
+
+use crate::protocol::{
+    socket_tag_name, CompressedSlots, ContactInfo, CrdsData, CrdsFilter, CrdsValue, CrdsValueLabel,
+    DuplicateShred, DuplicateShredIndex, EpochSlots, EpochSlotsIndex, IncrementalSnapshotHashes,
+    LegacyContactInfo, LowestSlot, NodeInstance, NodeVersion, Ping, Pong, Protocol, PruneData,
+    ShredType, Slot, SnapshotHashes, Vote, VoteIndex,
+};
+use crate::vote_parser::ParsedVote;
+
+// the raw bytes as a lowercase hex string
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+// the blocks of a bit vector as hex, each one in little endian as on the wire
+fn bits_hex(bits: &BitVec<u64>) -> String {
+    let bytes = (0..bits.block_len())
+        .flat_map(|index| bits.get_block(index).to_le_bytes())
+        .collect::<Vec<_>>();
+    hex(&bytes)
+}
+
+fn slot_hashes(hashes: &[(Slot, Hash)]) -> Vec<(Slot, String)> {
+    hashes
+        .iter()
+        .map(|(slot, hash)| (*slot, hash.to_string()))
+        .collect()
+}
+
+/// The JSON form of a gossip message, e.g.
+/// `serde_json::to_string(&ProtocolView::from(&protocol))`.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub enum ProtocolView {
+    PullRequest(CrdsFilterView, Box<CrdsValueView>),
+    PullResponse(String, Vec<CrdsValueView>),
+    PushMessage(String, Vec<CrdsValueView>),
+    PruneMessage(String, PruneDataView),
+    PingMessage(PingView),
+    PongMessage(PongView),
+}
+
+/// The bloom filter bits as hex, `num_bits` long.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct CrdsFilterView {
+    pub mask: String,
+    pub mask_bits: u32,
+    pub keys: Vec<u64>,
+    pub bits: String,
+    pub num_bits: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct PingView {
+    pub from: String,
+    pub token: String,
+    pub signature: String,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct PongView {
+    pub from: String,
+    pub hash: String,
+    pub signature: String,
+}
+
+/// The JSON form of a crds value, e.g. `serde_json::to_string(&CrdsValueView::from(&value))`:
+/// the pubkeys, hashes and signatures as base58 strings, the socket addresses
+/// as strings, the tokens and raw bytes as hex and the epoch slots expanded
+/// into the list of slots.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct CrdsValueView {
+    pub signature: String,
+    pub data: CrdsDataView,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub enum CrdsDataView {
+    LegacyContactInfo(LegacyContactInfoView),
+    Vote(VoteIndex, VoteView),
+    LowestSlot(u8, LowestSlotView),
+    SnapshotHashes(SnapshotHashesView),
+    AccountsHashes(SnapshotHashesView),
+    EpochSlots(EpochSlotsIndex, EpochSlotsView),
+    LegacyVersion(VersionView),
+    Version(VersionView),
+    NodeInstance(NodeInstanceView),
+    DuplicateShred(DuplicateShredIndex, DuplicateShredView),
+    IncrementalSnapshotHashes(IncrementalSnapshotHashesView),
+    ContactInfo(ContactInfoView),
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct LegacyContactInfoView {
+    pub id: String,
+    pub gossip: SocketAddr,
+    pub tvu: SocketAddr,
+    pub tvu_forwards: SocketAddr,
+    pub repair: SocketAddr,
+    pub tpu: SocketAddr,
+    pub tpu_forwards: SocketAddr,
+    pub tpu_vote: SocketAddr,
+    pub rpc: SocketAddr,
+    pub rpc_pubsub: SocketAddr,
+    pub serve_repair: SocketAddr,
+    pub wallclock: u64,
+    pub shred_version: u16,
+}
+
+/// The sockets are resolved and named by their tag, the unknown tags as
+/// `unknown:<tag>`.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct ContactInfoView {
+    pub pubkey: String,
+    pub wallclock: u64,
+    pub outset: u64,
+    pub shred_version: u16,
+    pub version: ContactInfoVersionView,
+    pub addrs: Vec<IpAddr>,
+    pub sockets: BTreeMap<String, SocketAddr>,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct ContactInfoVersionView {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub commit: String,
+    pub feature_set: String,
+    pub client: u16,
+}
+
+/// The vote transaction is shown by its signature and the parsed vote, if any.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct VoteView {
+    pub from: String,
+    pub signature: Option<String>,
+    pub vote: Option<ParsedVoteView>,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct ParsedVoteView {
+    pub vote_account: String,
+    pub slots: Vec<Slot>,
+    pub root: Option<Slot>,
+    pub hash: String,
+    pub timestamp: Option<UnixTimestamp>,
+    pub switch_proof_hash: Option<String>,
+}
+
+/// The deprecated fields, always empty, are left out.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct LowestSlotView {
+    pub from: String,
+    pub lowest: Slot,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct SnapshotHashesView {
+    pub from: String,
+    pub hashes: Vec<(Slot, String)>,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct IncrementalSnapshotHashesView {
+    pub from: String,
+    pub base: (Slot, String),
+    pub hashes: Vec<(Slot, String)>,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct EpochSlotsView {
+    pub from: String,
+    pub slots: Vec<CompressedSlotsView>,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub enum CompressedSlotsView {
+    Flate2(SlotsView),
+    Uncompressed(SlotsView),
+}
+
+/// The slots failing to inflate are left compressed, as hex.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct SlotsView {
+    pub first_slot: Slot,
+    pub num: usize,
+    pub slots: Vec<Slot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compressed: Option<String>,
+}
+
+/// Both the legacy and the current version values.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct VersionView {
+    pub from: String,
+    pub version: String,
+    pub commit: Option<String>,
+    pub feature_set: Option<String>,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct NodeInstanceView {
+    pub from: String,
+    pub timestamp: u64,
+    pub token: String,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct DuplicateShredView {
+    pub from: String,
+    pub slot: Slot,
+    pub shred_index: u32,
+    pub shred_type: &'static str,
+    pub num_chunks: u8,
+    pub chunk_index: u8,
+    pub chunk: String,
+    pub wallclock: u64,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub enum CrdsValueLabelView {
+    LegacyContactInfo(String),
+    Vote(VoteIndex, String),
+    LowestSlot(String),
+    SnapshotHashes(String),
+    AccountsHashes(String),
+    EpochSlots(EpochSlotsIndex, String),
+    LegacyVersion(String),
+    Version(String),
+    NodeInstance(String),
+    DuplicateShred(DuplicateShredIndex, String),
+    IncrementalSnapshotHashes(String),
+    ContactInfo(String),
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct PruneDataView {
+    pub pubkey: String,
+    pub prunes: Vec<String>,
+    pub signature: String,
+    pub destination: String,
+    pub wallclock: u64,
+}
+
+impl From<&Protocol> for ProtocolView {
+    fn from(protocol: &Protocol) -> Self {
+        fn values(values: &[CrdsValue]) -> Vec<CrdsValueView> {
+            values.iter().map(CrdsValueView::from).collect()
+        }
+
+        match protocol {
+            Protocol::PullRequest(filter, value) => {
+                ProtocolView::PullRequest(filter.into(), Box::new(value.into()))
+            }
+            Protocol::PullResponse(from, response) => {
+                ProtocolView::PullResponse(from.to_string(), values(response))
+            }
+            Protocol::PushMessage(from, messages) => {
+                ProtocolView::PushMessage(from.to_string(), values(messages))
+            }
+            Protocol::PruneMessage(from, data) => {
+                ProtocolView::PruneMessage(from.to_string(), data.into())
+            }
+            Protocol::PingMessage(ping) => ProtocolView::PingMessage(ping.into()),
+            Protocol::PongMessage(pong) => ProtocolView::PongMessage(pong.into()),
+        }
+    }
+}
+
+impl From<&CrdsFilter> for CrdsFilterView {
+    fn from(filter: &CrdsFilter) -> Self {
+        CrdsFilterView {
+            mask: format!("{:016x}", filter.mask),
+            mask_bits: filter.mask_bits,
+            keys: filter.filter.keys.clone(),
+            bits: bits_hex(&filter.filter.bits),
+            num_bits: filter.filter.bits.len(),
+        }
+    }
+}
+
+impl From<&Ping> for PingView {
+    fn from(ping: &Ping) -> Self {
+        PingView {
+            from: ping.from.to_string(),
+            token: hex(ping.token()),
+            signature: ping.signature().to_string(),
+        }
+    }
+}
+
+impl From<&Pong> for PongView {
+    fn from(pong: &Pong) -> Self {
+        PongView {
+            from: pong.from.to_string(),
+            hash: pong.hash().to_string(),
+            signature: pong.signature().to_string(),
+        }
+    }
+}
+
+impl From<&CrdsValue> for CrdsValueView {
+    fn from(value: &CrdsValue) -> Self {
+        CrdsValueView {
+            signature: value.signature.to_string(),
+            data: CrdsDataView::from(&value.data),
+        }
+    }
+}
+
+impl From<&CrdsData> for CrdsDataView {
+    fn from(data: &CrdsData) -> Self {
+        match data {
+            CrdsData::LegacyContactInfo(info) => {
+                CrdsDataView::LegacyContactInfo(info.as_ref().into())
+            }
+            CrdsData::Vote(index, vote) => CrdsDataView::Vote(*index, vote.into()),
+            CrdsData::LowestSlot(index, slot) => CrdsDataView::LowestSlot(*index, slot.into()),
+            CrdsData::SnapshotHashes(hashes) => CrdsDataView::SnapshotHashes(hashes.into()),
+            CrdsData::AccountsHashes(hashes) => CrdsDataView::AccountsHashes(hashes.into()),
+            CrdsData::EpochSlots(index, slots) => CrdsDataView::EpochSlots(*index, slots.into()),
+            CrdsData::LegacyVersion(version) => CrdsDataView::LegacyVersion(VersionView::new(
+                &version.from.to_string(),
+                &NodeVersion::from(&version.version),
+                version.wallclock,
+            )),
+            CrdsData::Version(version) => CrdsDataView::Version(VersionView::new(
+                &version.from.to_string(),
+                &NodeVersion::from(&version.version),
+                version.wallclock,
+            )),
+            CrdsData::NodeInstance(instance) => CrdsDataView::NodeInstance(instance.into()),
+            CrdsData::DuplicateShred(index, shred) => {
+                CrdsDataView::DuplicateShred(*index, shred.into())
+            }
+            CrdsData::IncrementalSnapshotHashes(hashes) => {
+                CrdsDataView::IncrementalSnapshotHashes(hashes.into())
+            }
+            CrdsData::ContactInfo(info) => CrdsDataView::ContactInfo(info.as_ref().into()),
+        }
+    }
+}
+
+impl From<&LegacyContactInfo> for LegacyContactInfoView {
+    fn from(info: &LegacyContactInfo) -> Self {
+        LegacyContactInfoView {
+            id: info.id.to_string(),
+            gossip: info.gossip,
+            tvu: info.tvu,
+            tvu_forwards: info.tvu_forwards,
+            repair: info.repair,
+            tpu: info.tpu,
+            tpu_forwards: info.tpu_forwards,
+            tpu_vote: info.tpu_vote,
+            rpc: info.rpc,
+            rpc_pubsub: info.rpc_pubsub,
+            serve_repair: info.serve_repair,
+            wallclock: info.wallclock,
+            shred_version: info.shred_version,
+        }
+    }
+}
+
+impl From<&ContactInfo> for ContactInfoView {
+    fn from(info: &ContactInfo) -> Self {
+        let sockets = info
+            .resolve_sockets()
+            .into_iter()
+            .map(|(tag, addr)| match socket_tag_name(tag) {
+                "unknown" => (format!("unknown:{tag}"), addr),
+                name => (name.to_string(), addr),
+            })
+            .collect();
+
+        ContactInfoView {
+            pubkey: info.pubkey.to_string(),
+            wallclock: info.wallclock,
+            outset: info.outset,
+            shred_version: info.shred_version,
+            version: ContactInfoVersionView {
+                major: info.version.major,
+                minor: info.version.minor,
+                patch: info.version.patch,
+                commit: format!("{:08x}", info.version.commit),
+                feature_set: format!("{:08x}", info.version.feature_set),
+                client: info.version.client,
+            },
+            addrs: info.addrs.clone(),
+            sockets,
+        }
+    }
+}
+
+impl From<&Vote> for VoteView {
+    fn from(vote: &Vote) -> Self {
+        VoteView {
+            from: vote.from.to_string(),
+            signature: vote
+                .transaction()
+                .signatures
+                .first()
+                .map(ToString::to_string),
+            vote: vote.parse().as_ref().map(ParsedVoteView::from),
+            wallclock: vote.wallclock,
+        }
+    }
+}
+
+impl From<&ParsedVote> for ParsedVoteView {
+    fn from(vote: &ParsedVote) -> Self {
+        ParsedVoteView {
+            vote_account: vote.vote_account.to_string(),
+            slots: vote.slots.clone(),
+            root: vote.root,
+            hash: vote.hash.to_string(),
+            timestamp: vote.timestamp,
+            switch_proof_hash: vote.switch_proof_hash.map(|hash| hash.to_string()),
+        }
+    }
+}
+
+impl From<&LowestSlot> for LowestSlotView {
+    fn from(slot: &LowestSlot) -> Self {
+        LowestSlotView {
+            from: slot.from.to_string(),
+            lowest: slot.lowest,
+            wallclock: slot.wallclock,
+        }
+    }
+}
+
+impl From<&SnapshotHashes> for SnapshotHashesView {
+    fn from(hashes: &SnapshotHashes) -> Self {
+        SnapshotHashesView {
+            from: hashes.from.to_string(),
+            hashes: slot_hashes(&hashes.hashes),
+            wallclock: hashes.wallclock,
+        }
+    }
+}
+
+impl From<&IncrementalSnapshotHashes> for IncrementalSnapshotHashesView {
+    fn from(hashes: &IncrementalSnapshotHashes) -> Self {
+        IncrementalSnapshotHashesView {
+            from: hashes.from.to_string(),
+            base: (hashes.base.0, hashes.base.1.to_string()),
+            hashes: slot_hashes(&hashes.hashes),
+            wallclock: hashes.wallclock,
+        }
+    }
+}
+
+impl From<&EpochSlots> for EpochSlotsView {
+    fn from(slots: &EpochSlots) -> Self {
+        EpochSlotsView {
+            from: slots.from.to_string(),
+            slots: slots.slots.iter().map(CompressedSlotsView::from).collect(),
+            wallclock: slots.wallclock,
+        }
+    }
+}
+
+impl From<&CompressedSlots> for CompressedSlotsView {
+    fn from(slots: &CompressedSlots) -> Self {
+        match slots {
+            CompressedSlots::Flate2(flate2) => {
+                let (slots, compressed) = match flate2.inflate() {
+                    Ok(uncompressed) => (uncompressed.to_slots(), None),
+                    Err(_) => (Vec::new(), Some(hex(&flate2.compressed))),
+                };
+                CompressedSlotsView::Flate2(SlotsView {
+                    first_slot: flate2.first_slot,
+                    num: flate2.num,
+                    slots,
+                    compressed,
+                })
+            }
+            CompressedSlots::Uncompressed(uncompressed) => {
+                CompressedSlotsView::Uncompressed(SlotsView {
+                    first_slot: uncompressed.first_slot,
+                    num: uncompressed.num,
+                    slots: uncompressed.to_slots(),
+                    compressed: None,
+                })
+            }
+        }
+    }
+}
+
+impl VersionView {
+    fn new(from: &str, version: &NodeVersion, wallclock: u64) -> Self {
+        VersionView {
+            from: from.to_string(),
+            version: version.semver(),
+            commit: version.commit_hex(),
+            feature_set: version.feature_set_hex(),
+            wallclock,
+        }
+    }
+}
+
+impl From<&NodeInstance> for NodeInstanceView {
+    fn from(instance: &NodeInstance) -> Self {
+        NodeInstanceView {
+            from: instance.from.to_string(),
+            timestamp: instance.timestamp,
+            token: format!("{:x}", instance.token),
+            wallclock: instance.wallclock,
+        }
+    }
+}
+
+impl From<&DuplicateShred> for DuplicateShredView {
+    fn from(shred: &DuplicateShred) -> Self {
+        DuplicateShredView {
+            from: shred.from.to_string(),
+            slot: shred.slot,
+            shred_index: shred.shred_index,
+            shred_type: match shred.shred_type {
+                ShredType::Data => "Data",
+                ShredType::Code => "Code",
+            },
+            num_chunks: shred.num_chunks,
+            chunk_index: shred.chunk_index,
+            chunk: hex(&shred.chunk),
+            wallclock: shred.wallclock,
+        }
+    }
+}
+
+impl From<&CrdsValueLabel> for CrdsValueLabelView {
+    fn from(label: &CrdsValueLabel) -> Self {
+        let pubkey = label.pubkey().to_string();
+        match label {
+            CrdsValueLabel::LegacyContactInfo(_) => CrdsValueLabelView::LegacyContactInfo(pubkey),
+            CrdsValueLabel::Vote(index, _) => CrdsValueLabelView::Vote(*index, pubkey),
+            CrdsValueLabel::LowestSlot(_) => CrdsValueLabelView::LowestSlot(pubkey),
+            CrdsValueLabel::SnapshotHashes(_) => CrdsValueLabelView::SnapshotHashes(pubkey),
+            CrdsValueLabel::AccountsHashes(_) => CrdsValueLabelView::AccountsHashes(pubkey),
+            CrdsValueLabel::EpochSlots(index, _) => CrdsValueLabelView::EpochSlots(*index, pubkey),
+            CrdsValueLabel::LegacyVersion(_) => CrdsValueLabelView::LegacyVersion(pubkey),
+            CrdsValueLabel::Version(_) => CrdsValueLabelView::Version(pubkey),
+            CrdsValueLabel::NodeInstance(_) => CrdsValueLabelView::NodeInstance(pubkey),
+            CrdsValueLabel::DuplicateShred(index, _) => {
+                CrdsValueLabelView::DuplicateShred(*index, pubkey)
+            }
+            CrdsValueLabel::IncrementalSnapshotHashes(_) => {
+                CrdsValueLabelView::IncrementalSnapshotHashes(pubkey)
+            }
+            CrdsValueLabel::ContactInfo(_) => CrdsValueLabelView::ContactInfo(pubkey),
+        }
+    }
+}
+
+impl From<&PruneData> for PruneDataView {
+    fn from(data: &PruneData) -> Self {
+        PruneDataView {
+            pubkey: data.pubkey.to_string(),
+            prunes: data.prunes.iter().map(ToString::to_string).collect(),
+            signature: data.signature.to_string(),
+            destination: data.destination.to_string(),
+            wallclock: data.wallclock,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{
+        ContactInfoVersion, Flate2, Uncompressed, SOCKET_TAG_GOSSIP, SOCKET_TAG_TPU_QUIC,
+        SOCKET_TAG_TVU,
+    };
+    use serde_json::{json, to_value};
+    use solana_bloom::bloom::Bloom;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    #[test]
+    fn test_json_contact_info() {
+        let keypair = Keypair::new();
+        let info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            gossip: "10.0.0.1:8001".parse().unwrap(),
+            wallclock: 1_700_000_000_000,
+            ..LegacyContactInfo::default()
+        };
+        let value = CrdsValue::new_signed(CrdsData::LegacyContactInfo(Box::new(info)), &keypair);
+
+        let json = to_value(CrdsValueView::from(&value)).unwrap();
+        assert_eq!(json["signature"], json!(value.signature.to_string()));
+        let info = &json["data"]["LegacyContactInfo"];
+        assert_eq!(info["id"], json!(keypair.pubkey().to_string()));
+        assert_eq!(info["gossip"], json!("10.0.0.1:8001"));
+        assert_eq!(info["tvu"], json!("0.0.0.0:0"));
+        assert_eq!(info["wallclock"], json!(1_700_000_000_000_u64));
+    }
+
+    #[test]
+    fn test_json_contact_info_new() {
+        let keypair = Keypair::new();
+        let mut info = ContactInfo::new(keypair.pubkey(), 1_700_000_000_000, 50093);
+        info.version = ContactInfoVersion {
+            major: 1,
+            minor: 16,
+            patch: 14,
+            commit: 0xdead_beef,
+            feature_set: 0x1234,
+            client: 1,
+        };
+        info.set_socket(SOCKET_TAG_GOSSIP, "10.0.0.1:8001".parse().unwrap());
+        info.set_socket(SOCKET_TAG_TVU, "10.0.0.1:8000".parse().unwrap());
+        info.set_socket(SOCKET_TAG_TPU_QUIC, "10.0.0.2:8009".parse().unwrap());
+        info.set_socket(42, "10.0.0.2:9000".parse().unwrap());
+        let value = CrdsValue::new_signed(CrdsData::ContactInfo(Box::new(info)), &keypair);
+
+        let json = to_value(CrdsValueView::from(&value)).unwrap();
+        assert_eq!(
+            json["data"]["ContactInfo"],
+            json!({
+                "pubkey": keypair.pubkey().to_string(),
+                "wallclock": 1_700_000_000_000_u64,
+                "outset": 1_700_000_000_000_u64,
+                "shred_version": 50093,
+                "version": {
+                    "major": 1,
+                    "minor": 16,
+                    "patch": 14,
+                    "commit": "deadbeef",
+                    "feature_set": "00001234",
+                    "client": 1,
+                },
+                "addrs": ["10.0.0.1", "10.0.0.2"],
+                "sockets": {
+                    "gossip": "10.0.0.1:8001",
+                    "tvu": "10.0.0.1:8000",
+                    "tpu_quic": "10.0.0.2:8009",
+                    "unknown:42": "10.0.0.2:9000",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_slots() {
+        let mut bits = BitVec::new_fill(false, 16);
+        bits.set(1, true);
+        bits.set(10, true);
+        let slots = EpochSlots {
+            from: Pubkey::default(),
+            slots: vec![
+                CompressedSlots::Uncompressed(Uncompressed {
+                    first_slot: 1000,
+                    num: 16,
+                    slots: bits,
+                }),
+                CompressedSlots::Flate2(Flate2 {
+                    first_slot: 3000,
+                    num: 16,
+                    compressed: vec![0xff; 4],
+                }),
+            ],
+            wallclock: 0,
+        };
+
+        let json = to_value(EpochSlotsView::from(&slots)).unwrap();
+        assert_eq!(json["from"], json!("11111111111111111111111111111111"));
+        assert_eq!(
+            json["slots"][0],
+            json!({"Uncompressed": {"first_slot": 1000, "num": 16, "slots": [1001, 1010]}})
+        );
+        // failing to inflate, the bytes are kept
+        assert_eq!(
+            json["slots"][1],
+            json!({"Flate2": {"first_slot": 3000, "num": 16, "slots": [], "compressed": "ffffffff"}})
+        );
+    }
+
+    #[test]
+    fn test_json_protocol() {
+        let keypair = Keypair::new();
+        let mut bloom = Bloom::new(70, vec![1, 2]);
+        bloom.bits.set(0, true);
+        bloom.bits.set(65, true);
+        let filter = CrdsFilter {
+            filter: bloom,
+            mask: 0x7fff_ffff_ffff_ffff,
+            mask_bits: 1,
+        };
+        let info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            ..LegacyContactInfo::default()
+        };
+        let value = CrdsValue::new_signed(CrdsData::LegacyContactInfo(Box::new(info)), &keypair);
+
+        let json = to_value(ProtocolView::from(&Protocol::PullRequest(filter, value))).unwrap();
+        assert_eq!(
+            json["PullRequest"][0],
+            json!({
+                "mask": "7fffffffffffffff",
+                "mask_bits": 1,
+                "keys": [1, 2],
+                "bits": "01000000000000000200000000000000",
+                "num_bits": 70,
+            })
+        );
+        assert_eq!(
+            json["PullRequest"][1]["data"]["LegacyContactInfo"]["id"],
+            json!(keypair.pubkey().to_string())
+        );
+
+        let request = Ping::new([7; 32], &keypair).unwrap();
+        let response = Pong::new(&request, &keypair).unwrap();
+        assert_eq!(
+            to_value(ProtocolView::from(&Protocol::PingMessage(request.clone()))).unwrap(),
+            json!({"PingMessage": {
+                "from": keypair.pubkey().to_string(),
+                "token": "07".repeat(32),
+                "signature": request.signature().to_string(),
+            }})
+        );
+        assert_eq!(
+            to_value(ProtocolView::from(&Protocol::PongMessage(response.clone()))).unwrap(),
+            json!({"PongMessage": {
+                "from": keypair.pubkey().to_string(),
+                "hash": response.hash().to_string(),
+                "signature": response.signature().to_string(),
+            }})
+        );
+    }
+
+    #[test]
+    fn test_json_labels() {
+        let pubkey = Pubkey::new_unique();
+        assert_eq!(
+            to_value(CrdsValueLabelView::from(&CrdsValueLabel::Vote(3, pubkey))).unwrap(),
+            json!({"Vote": [3, pubkey.to_string()]})
+        );
+        assert_eq!(
+            to_value(CrdsValueLabelView::from(&CrdsValueLabel::NodeInstance(
+                pubkey
+            )))
+            .unwrap(),
+            json!({"NodeInstance": pubkey.to_string()})
+        );
+    }
+}
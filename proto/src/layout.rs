@@ -0,0 +1,782 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{self, Write},
+    io::{self, Read},
+    rc::Rc,
+};
+
+use bincode::Options;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde_derive::Serialize;
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature};
+
+use crate::wire::PACKET_DATA_SIZE;
+
+/// A decoded field and the bytes it spans in the packet.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ty: Option<&'static str>,
+    pub offset: usize,
+    pub len: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Field>,
+}
+
+impl Field {
+    fn new(name: String, offset: usize) -> Self {
+        Field {
+            name,
+            ty: None,
+            offset,
+            len: 0,
+            value: None,
+            children: vec![],
+        }
+    }
+
+    /// Returns the deepest fields spanning the offset, from the outermost one.
+    pub fn path_at(&self, offset: usize) -> Vec<&Field> {
+        let mut path = vec![self];
+        let mut field = self;
+        while let Some(child) = field
+            .children
+            .iter()
+            .find(|child| child.offset <= offset && offset < child.offset + child.len)
+        {
+            path.push(child);
+            field = child;
+        }
+        path
+    }
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct LayoutError {
+    /// where the decoding stopped
+    pub offset: usize,
+    pub message: String,
+}
+
+/// The fields decoded from a packet, up to the error if the decoding failed.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+pub struct Layout {
+    pub root: Field,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<LayoutError>,
+}
+
+impl Layout {
+    /// Decodes the bytes as `Payload::deserialize_slice` does, recording the
+    /// offsets of every field.
+    pub fn of<T: DeserializeOwned>(bytes: &[u8]) -> Self {
+        let pos = Rc::new(Cell::new(0));
+        let tracer = Tracer {
+            pos: pos.clone(),
+            stack: RefCell::new(vec![]),
+        };
+        let options = bincode::options()
+            .with_limit(PACKET_DATA_SIZE as u64)
+            .with_fixint_encoding();
+        let mut deserializer =
+            bincode::Deserializer::with_reader(CountingReader { bytes, pos }, options);
+
+        let name = std::any::type_name::<T>().rsplit("::").next().unwrap_or("");
+        tracer.open(name.to_string());
+        let result = T::deserialize(TracingDeserializer {
+            inner: &mut deserializer,
+            tracer: &tracer,
+        });
+
+        let offset = tracer.pos.get();
+        let message = match result {
+            Ok(_) if offset < bytes.len() => Some("trailing bytes".to_string()),
+            Ok(_) => None,
+            Err(err) => Some(err.to_string()),
+        };
+
+        Layout {
+            root: tracer.finish(),
+            error: message.map(|message| LayoutError { offset, message }),
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_field(f: &mut fmt::Formatter<'_>, field: &Field, depth: usize) -> fmt::Result {
+            write!(
+                f,
+                "{:04}..{:04} {:indent$}{}",
+                field.offset,
+                field.offset + field.len,
+                "",
+                field.name,
+                indent = depth * 2
+            )?;
+            if let Some(ty) = field.ty {
+                write!(f, " <{ty}>")?;
+            }
+            if let Some(value) = &field.value {
+                write!(f, " = {value}")?;
+            }
+            writeln!(f)?;
+
+            for child in &field.children {
+                write_field(f, child, depth + 1)?;
+            }
+            Ok(())
+        }
+
+        write_field(f, &self.root, 0)?;
+        if let Some(error) = &self.error {
+            writeln!(f, "error at offset {}: {}", error.offset, error.message)?;
+        }
+        Ok(())
+    }
+}
+
+// counts the bytes consumed by the deserializer
+struct CountingReader<'a> {
+    bytes: &'a [u8],
+    pos: Rc<Cell<usize>>,
+}
+
+impl Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.bytes.read(buf)?;
+        self.pos.set(self.pos.get() + len);
+        Ok(len)
+    }
+}
+
+// the fields being decoded, from the root to the current one
+struct Tracer {
+    pos: Rc<Cell<usize>>,
+    stack: RefCell<Vec<Field>>,
+}
+
+impl Tracer {
+    fn open(&self, name: String) {
+        let field = Field::new(name, self.pos.get());
+        self.stack.borrow_mut().push(field);
+    }
+
+    fn close(&self) {
+        let mut stack = self.stack.borrow_mut();
+        if stack.len() < 2 {
+            return;
+        }
+        if let Some(mut field) = stack.pop() {
+            field.len = self.pos.get() - field.offset;
+            collapse_bytes(&mut field);
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(field);
+            }
+        }
+    }
+
+    // closes the fields left open by an error as well, dropping the ones
+    // nothing was decoded of
+    fn finish(&self) -> Field {
+        while self.stack.borrow().len() > 1 {
+            let empty = matches!(
+                self.stack.borrow().last(),
+                Some(field) if field.offset == self.pos.get()
+                    && field.value.is_none()
+                    && field.children.is_empty()
+            );
+            if empty {
+                self.stack.borrow_mut().pop();
+            } else {
+                self.close();
+            }
+        }
+        let mut root = self
+            .stack
+            .borrow_mut()
+            .pop()
+            .unwrap_or_else(|| Field::new(String::new(), 0));
+        root.len = self.pos.get() - root.offset;
+        collapse_bytes(&mut root);
+        root
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Field)) {
+        if let Some(field) = self.stack.borrow_mut().last_mut() {
+            f(field);
+        }
+    }
+
+    // the outermost type is kept, e.g. the newtype wrapping an array
+    fn set_type(&self, ty: &'static str) {
+        self.update(|field| {
+            if field.ty.is_none() {
+                field.ty = Some(ty);
+            }
+        });
+    }
+
+    fn set_value(&self, value: String) {
+        self.update(|field| field.value = Some(value));
+    }
+
+    // the variant index was decoded as the value of the enum
+    fn set_variant(&self, variants: &'static [&'static str]) {
+        self.update(|field| {
+            let name = field
+                .value
+                .as_ref()
+                .and_then(|value| value.parse::<usize>().ok())
+                .and_then(|index| variants.get(index));
+            if let Some(name) = name {
+                field.value = Some((*name).to_string());
+            }
+        });
+    }
+}
+
+// shows the byte arrays as a single value instead of a field per byte
+fn collapse_bytes(field: &mut Field) {
+    if field.children.is_empty()
+        || field
+            .children
+            .iter()
+            .any(|child| child.ty != Some("u8") || !child.children.is_empty())
+    {
+        return;
+    }
+
+    let bytes = field
+        .children
+        .iter()
+        .filter_map(|child| child.value.as_ref()?.parse::<u8>().ok())
+        .collect::<Vec<_>>();
+    let value = match field.ty {
+        Some("Pubkey") => Pubkey::try_from(bytes.as_slice())
+            .ok()
+            .map(|v| v.to_string()),
+        Some("Hash") if bytes.len() == 32 => Some(Hash::new(&bytes).to_string()),
+        Some("Signature") => Signature::try_from(bytes.as_slice())
+            .ok()
+            .map(|v| v.to_string()),
+        _ => None,
+    };
+
+    field.value = Some(value.unwrap_or_else(|| to_hex(&bytes)));
+    field.children.clear();
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+// how the elements of a sequence are named
+#[derive(Clone, Copy)]
+enum Names {
+    Seq,
+    Tuple,
+    Fields(&'static [&'static str]),
+    Variants(&'static [&'static str]),
+}
+
+struct TracingDeserializer<'t, D> {
+    inner: D,
+    tracer: &'t Tracer,
+}
+
+struct TracingVisitor<'t, V> {
+    inner: V,
+    tracer: &'t Tracer,
+    names: Names,
+}
+
+struct TracingSeed<'t, S> {
+    inner: S,
+    tracer: &'t Tracer,
+    name: Option<String>,
+}
+
+struct TracingSeq<'t, A> {
+    inner: A,
+    tracer: &'t Tracer,
+    names: Names,
+    index: usize,
+}
+
+struct TracingEnum<'t, A> {
+    inner: A,
+    tracer: &'t Tracer,
+    variants: &'static [&'static str],
+}
+
+struct TracingVariant<'t, A> {
+    inner: A,
+    tracer: &'t Tracer,
+}
+
+impl<'t, D> TracingDeserializer<'t, D> {
+    fn visitor<V>(&self, inner: V, names: Names) -> TracingVisitor<'t, V> {
+        TracingVisitor {
+            inner,
+            tracer: self.tracer,
+            names,
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident),*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+            let visitor = self.visitor(visitor, Names::Tuple);
+            self.inner.$method(visitor)
+        }
+    )*};
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for TracingDeserializer<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize!(
+        deserialize_any,
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_i128,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_u128,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_option,
+        deserialize_unit,
+        deserialize_map,
+        deserialize_identifier,
+        deserialize_ignored_any
+    );
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        let visitor = self.visitor(visitor, Names::Seq);
+        self.inner.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        let visitor = self.visitor(visitor, Names::Tuple);
+        self.inner.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.tracer.set_type(name);
+        let visitor = self.visitor(visitor, Names::Tuple);
+        self.inner.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.tracer.set_type(name);
+        let visitor = self.visitor(visitor, Names::Tuple);
+        self.inner.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.tracer.set_type(name);
+        let visitor = self.visitor(visitor, Names::Tuple);
+        self.inner.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.tracer.set_type(name);
+        let visitor = self.visitor(visitor, Names::Fields(fields));
+        self.inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        self.tracer.set_type(name);
+        let visitor = self.visitor(visitor, Names::Variants(variants));
+        self.inner.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident: $ty:ty => $label:literal),*) => {$(
+        fn $method<E: de::Error>(self, v: $ty) -> Result<V::Value, E> {
+            self.tracer.set_type($label);
+            self.tracer.set_value(v.to_string());
+            self.inner.$method(v)
+        }
+    )*};
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for TracingVisitor<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    forward_visit!(
+        visit_bool: bool => "bool",
+        visit_i8: i8 => "i8",
+        visit_i16: i16 => "i16",
+        visit_i32: i32 => "i32",
+        visit_i64: i64 => "i64",
+        visit_i128: i128 => "i128",
+        visit_u8: u8 => "u8",
+        visit_u16: u16 => "u16",
+        visit_u32: u32 => "u32",
+        visit_u64: u64 => "u64",
+        visit_u128: u128 => "u128",
+        visit_f32: f32 => "f32",
+        visit_f64: f64 => "f64",
+        visit_char: char => "char",
+        visit_str: &str => "str",
+        visit_borrowed_str: &'de str => "str"
+    );
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<V::Value, E> {
+        self.tracer.set_type("str");
+        self.tracer.set_value(v.clone());
+        self.inner.visit_string(v)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<V::Value, E> {
+        self.tracer.set_value(to_hex(v));
+        self.inner.visit_bytes(v)
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<V::Value, E> {
+        self.tracer.set_value(to_hex(v));
+        self.inner.visit_borrowed_bytes(v)
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<V::Value, E> {
+        self.tracer.set_value(to_hex(&v));
+        self.inner.visit_byte_buf(v)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.tracer.set_value("None".to_string());
+        self.inner.visit_none()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.inner.visit_some(TracingDeserializer {
+            inner: deserializer,
+            tracer: self.tracer,
+        })
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        self.inner.visit_newtype_struct(TracingDeserializer {
+            inner: deserializer,
+            tracer: self.tracer,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        if let (Names::Seq, Some(len)) = (self.names, seq.size_hint()) {
+            self.tracer.set_value(format!("len:{len}"));
+        }
+        self.inner.visit_seq(TracingSeq {
+            inner: seq,
+            tracer: self.tracer,
+            names: self.names,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        if let Some(len) = map.size_hint() {
+            self.tracer.set_value(format!("len:{len}"));
+        }
+        self.inner.visit_map(TracingSeq {
+            inner: map,
+            tracer: self.tracer,
+            names: self.names,
+            index: 0,
+        })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        let variants = match self.names {
+            Names::Variants(variants) => variants,
+            _ => &[],
+        };
+        self.inner.visit_enum(TracingEnum {
+            inner: data,
+            tracer: self.tracer,
+            variants,
+        })
+    }
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TracingSeed<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        let named = self.name.is_some();
+        if let Some(name) = self.name {
+            self.tracer.open(name);
+        }
+
+        // on error the field is left open, to be closed at the failing offset
+        let value = self.inner.deserialize(TracingDeserializer {
+            inner: deserializer,
+            tracer: self.tracer,
+        })?;
+
+        if named {
+            self.tracer.close();
+        }
+        Ok(value)
+    }
+}
+
+impl<'t, A> TracingSeq<'t, A> {
+    fn seed<S>(&self, inner: S, name: String) -> TracingSeed<'t, S> {
+        TracingSeed {
+            inner,
+            tracer: self.tracer,
+            name: Some(name),
+        }
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for TracingSeq<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, A::Error> {
+        let name = match self.names {
+            Names::Fields(fields) if self.index < fields.len() => fields[self.index].to_string(),
+            Names::Seq => format!("[{}]", self.index),
+            _ => self.index.to_string(),
+        };
+        self.index += 1;
+
+        let seed = self.seed(seed, name);
+        self.inner.next_element_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for TracingSeq<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, A::Error> {
+        let seed = self.seed(seed, format!("[{}].key", self.index));
+        self.inner.next_key_seed(seed)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, A::Error> {
+        let seed = self.seed(seed, format!("[{}].value", self.index));
+        self.index += 1;
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, 't, A: EnumAccess<'de>> EnumAccess<'de> for TracingEnum<'t, A> {
+    type Error = A::Error;
+    type Variant = TracingVariant<'t, A::Variant>;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, Self::Variant), A::Error> {
+        let seed = TracingSeed {
+            inner: seed,
+            tracer: self.tracer,
+            name: None,
+        };
+        let (value, variant) = self.inner.variant_seed(seed)?;
+        self.tracer.set_variant(self.variants);
+
+        Ok((
+            value,
+            TracingVariant {
+                inner: variant,
+                tracer: self.tracer,
+            },
+        ))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for TracingVariant<'_, A> {
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value, A::Error> {
+        self.inner.newtype_variant_seed(TracingSeed {
+            inner: seed,
+            tracer: self.tracer,
+            name: Some("0".to_string()),
+        })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.inner.tuple_variant(
+            len,
+            TracingVisitor {
+                inner: visitor,
+                tracer: self.tracer,
+                names: Names::Tuple,
+            },
+        )
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        self.inner.struct_variant(
+            fields,
+            TracingVisitor {
+                inner: visitor,
+                tracer: self.tracer,
+                names: Names::Fields(fields),
+            },
+        )
+    }
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::protocol::{Ping, Protocol};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_layout() {
+        let keypair = Keypair::new();
+        let ping = Ping::new([7; 32], &keypair).unwrap();
+        let bytes = bincode::serialize(&Protocol::PingMessage(ping)).unwrap();
+
+        let layout = Layout::of::<Protocol>(&bytes);
+        assert_eq!(layout.error, None);
+        assert_eq!(layout.root.name, "Protocol");
+        assert_eq!(layout.root.ty, Some("Protocol"));
+        assert_eq!(layout.root.value.as_deref(), Some("PingMessage"));
+        assert_eq!(layout.root.len, bytes.len());
+
+        let ping = &layout.root.children[0];
+        assert_eq!(ping.ty, Some("PingGeneric"));
+        let fields = ping
+            .children
+            .iter()
+            .map(|field| (field.name.as_str(), field.offset, field.len))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![("from", 4, 32), ("token", 36, 32), ("signature", 68, 64)]
+        );
+        assert_eq!(ping.children[0].value, Some(keypair.pubkey().to_string()));
+        assert_eq!(ping.children[1].value, Some("07".repeat(32)));
+
+        let path = layout.root.path_at(40);
+        let names = path
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Protocol", "0", "token"]);
+    }
+
+    #[test]
+    fn test_layout_error() {
+        let keypair = Keypair::new();
+        let ping = Ping::new([7; 32], &keypair).unwrap();
+        let bytes = bincode::serialize(&Protocol::PingMessage(ping)).unwrap();
+
+        // the signature is truncated
+        let layout = Layout::of::<Protocol>(&bytes[..100]);
+        let error = layout.error.unwrap();
+        assert_eq!(error.offset, 100);
+        let ping = &layout.root.children[0];
+        assert_eq!(ping.children.len(), 3);
+        assert_eq!((ping.children[2].offset, ping.children[2].len), (68, 32));
+
+        // unknown variant
+        let layout = Layout::of::<Protocol>(&[9, 0, 0, 0]);
+        let error = layout.error.unwrap();
+        assert_eq!(error.offset, 4);
+        assert_eq!(layout.root.value.as_deref(), Some("9"));
+
+        // trailing bytes
+        let mut bytes = bytes;
+        bytes.push(0);
+        let layout = Layout::of::<Protocol>(&bytes);
+        assert_eq!(layout.error.unwrap().offset, bytes.len() - 1);
+    }
+}
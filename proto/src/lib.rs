@@ -1,7 +1,14 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::must_use_candidate)]
+pub mod capture;
+pub mod crds;
+pub mod duplicate_shred;
 pub mod errors;
+pub mod json;
+pub mod layout;
+pub mod node_instance;
 pub mod protocol;
 pub mod utils;
+pub mod vote_parser;
 pub mod wire;
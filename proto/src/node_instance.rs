@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{crds::CRDS_TIMEOUT_MS, protocol::NodeInstance};
+
+/// The instance of a node currently running and the ones seen before.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NodeInstanceState {
+    /// token of the most recently created instance
+    pub token: u64,
+    /// creation timestamp of the most recently created instance
+    pub timestamp: u64,
+    pub restarts: u32,
+    /// token of another instance running with the same identity
+    pub duplicate: Option<u64>,
+    // token -> last wallclock
+    seen: HashMap<u64, u64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InstanceEvent {
+    Started {
+        pubkey: Pubkey,
+        token: u64,
+    },
+    /// a new instance has replaced the previous one
+    Restarted {
+        pubkey: Pubkey,
+        token: u64,
+    },
+    /// two instances are running at once with the same identity
+    Duplicate {
+        pubkey: Pubkey,
+        token: u64,
+        other: u64,
+    },
+}
+
+/// Tracks the `NodeInstance` values of the nodes to detect restarts and
+/// duplicate instances.
+#[derive(Default, Debug)]
+pub struct NodeInstances {
+    nodes: HashMap<Pubkey, NodeInstanceState>,
+}
+
+impl NodeInstances {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, pubkey: &Pubkey) -> Option<&NodeInstanceState> {
+        self.nodes.get(pubkey)
+    }
+
+    pub fn remove(&mut self, pubkey: &Pubkey) -> Option<NodeInstanceState> {
+        self.nodes.remove(pubkey)
+    }
+
+    /// The nodes running more than one instance.
+    pub fn duplicates(&self) -> impl Iterator<Item = &Pubkey> {
+        self.nodes
+            .iter()
+            .filter(|(_, state)| state.duplicate.is_some())
+            .map(|(pubkey, _)| pubkey)
+    }
+
+    /// A new token created later than the running one is a restart, any other
+    /// token still advertised is a duplicate instance.
+    pub fn observe(&mut self, instance: &NodeInstance) -> Option<InstanceEvent> {
+        let pubkey = instance.from;
+        let token = instance.token;
+
+        let Some(state) = self.nodes.get_mut(&pubkey) else {
+            self.nodes.insert(
+                pubkey,
+                NodeInstanceState {
+                    token,
+                    timestamp: instance.timestamp,
+                    restarts: 0,
+                    duplicate: None,
+                    seen: HashMap::from([(token, instance.wallclock)]),
+                },
+            );
+            return Some(InstanceEvent::Started { pubkey, token });
+        };
+
+        // forget the instances not advertised anymore
+        let min_wallclock = instance.wallclock.saturating_sub(CRDS_TIMEOUT_MS);
+        state
+            .seen
+            .retain(|_, wallclock| *wallclock >= min_wallclock);
+        let seen_before = state.seen.contains_key(&token);
+        state.seen.insert(token, instance.wallclock);
+
+        if token == state.token {
+            if let Some(other) = state.duplicate {
+                if !state.seen.contains_key(&other) {
+                    state.duplicate = None;
+                }
+            }
+            return None;
+        }
+
+        let previous = state.token;
+        if instance.timestamp > state.timestamp {
+            state.token = token;
+            state.timestamp = instance.timestamp;
+
+            if !seen_before {
+                state.restarts += 1;
+                return Some(InstanceEvent::Restarted { pubkey, token });
+            }
+        }
+
+        // the instance not created most recently is the duplicate one
+        let (duplicate, other) = if state.token == token {
+            (previous, previous)
+        } else {
+            (token, state.token)
+        };
+        if state.duplicate == Some(duplicate) {
+            return None;
+        }
+        state.duplicate = Some(duplicate);
+
+        Some(InstanceEvent::Duplicate {
+            pubkey,
+            token,
+            other,
+        })
+    }
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn new_instance(from: Pubkey, token: u64, timestamp: u64, wallclock: u64) -> NodeInstance {
+        NodeInstance {
+            from,
+            wallclock,
+            timestamp,
+            token,
+        }
+    }
+
+    #[test]
+    fn test_restart() {
+        let pubkey = Pubkey::new_unique();
+        let mut instances = NodeInstances::new();
+
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 1, 100, 1_000)),
+            Some(InstanceEvent::Started { pubkey, token: 1 })
+        );
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 1, 100, 2_000)),
+            None
+        );
+
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 2, 3_000, 3_000)),
+            Some(InstanceEvent::Restarted { pubkey, token: 2 })
+        );
+        let state = instances.get(&pubkey).unwrap();
+        assert_eq!((state.token, state.restarts, state.duplicate), (2, 1, None));
+
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 2, 3_000, 4_000)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let pubkey = Pubkey::new_unique();
+        let mut instances = NodeInstances::new();
+
+        instances.observe(&new_instance(pubkey, 1, 100, 1_000));
+        instances.observe(&new_instance(pubkey, 2, 200, 2_000));
+
+        // the first instance is still running
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 1, 100, 3_000)),
+            Some(InstanceEvent::Duplicate {
+                pubkey,
+                token: 1,
+                other: 2
+            })
+        );
+        assert_eq!(instances.get(&pubkey).unwrap().duplicate, Some(1));
+        assert_eq!(instances.duplicates().collect::<Vec<_>>(), vec![&pubkey]);
+
+        // reported once
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 2, 200, 4_000)),
+            None
+        );
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 1, 100, 5_000)),
+            None
+        );
+
+        // the first instance is gone
+        let wallclock = 5_000 + CRDS_TIMEOUT_MS + 1;
+        assert_eq!(
+            instances.observe(&new_instance(pubkey, 2, 200, wallclock)),
+            None
+        );
+        assert_eq!(instances.get(&pubkey).unwrap().duplicate, None);
+
+        assert!(instances.remove(&pubkey).is_some());
+        assert!(instances.get(&pubkey).is_none());
+    }
+}
@@ -1,11 +1,13 @@
 use std::{
     collections::BTreeSet,
     fmt,
-    net::{Ipv4Addr, SocketAddr},
+    io::Read,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
 };
 
 use bincode::serialize;
 use bv::BitVec;
+use flate2::read::DeflateDecoder;
 use serde::Serialize as SerdeSerialize;
 use serde_derive::{Deserialize, Serialize};
 
@@ -13,11 +15,13 @@ use solana_bloom::bloom::Bloom;
 use solana_sdk::{
     hash::{self, Hash},
     pubkey::Pubkey,
+    serde_varint, short_vec,
     signature::{Keypair, Signature, Signer},
     transaction::Transaction,
 };
 
 use crate::errors::Result;
+use crate::vote_parser::{parse_vote_transaction, ParsedVote};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct LegacyContactInfo {
@@ -72,6 +76,207 @@ impl Default for LegacyContactInfo {
     }
 }
 
+/// socket tags used by `ContactInfo` to identify each service endpoint
+pub const SOCKET_TAG_GOSSIP: u8 = 0;
+pub const SOCKET_TAG_SERVE_REPAIR_QUIC: u8 = 1;
+pub const SOCKET_TAG_RPC: u8 = 2;
+pub const SOCKET_TAG_RPC_PUBSUB: u8 = 3;
+pub const SOCKET_TAG_SERVE_REPAIR: u8 = 4;
+pub const SOCKET_TAG_TPU: u8 = 5;
+pub const SOCKET_TAG_TPU_FORWARDS: u8 = 6;
+pub const SOCKET_TAG_TPU_FORWARDS_QUIC: u8 = 7;
+pub const SOCKET_TAG_TPU_QUIC: u8 = 8;
+pub const SOCKET_TAG_TPU_VOTE: u8 = 9;
+pub const SOCKET_TAG_TVU: u8 = 10;
+pub const SOCKET_TAG_TVU_QUIC: u8 = 11;
+pub const SOCKET_TAG_TPU_VOTE_QUIC: u8 = 12;
+
+pub fn socket_tag_name(key: u8) -> &'static str {
+    match key {
+        SOCKET_TAG_GOSSIP => "gossip",
+        SOCKET_TAG_SERVE_REPAIR_QUIC => "serve_repair_quic",
+        SOCKET_TAG_RPC => "rpc",
+        SOCKET_TAG_RPC_PUBSUB => "rpc_pubsub",
+        SOCKET_TAG_SERVE_REPAIR => "serve_repair",
+        SOCKET_TAG_TPU => "tpu",
+        SOCKET_TAG_TPU_FORWARDS => "tpu_forwards",
+        SOCKET_TAG_TPU_FORWARDS_QUIC => "tpu_forwards_quic",
+        SOCKET_TAG_TPU_QUIC => "tpu_quic",
+        SOCKET_TAG_TPU_VOTE => "tpu_vote",
+        SOCKET_TAG_TVU => "tvu",
+        SOCKET_TAG_TVU_QUIC => "tvu_quic",
+        SOCKET_TAG_TPU_VOTE_QUIC => "tpu_vote_quic",
+        _ => "unknown",
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct ContactInfoVersion {
+    #[serde(with = "serde_varint")]
+    pub major: u16,
+    #[serde(with = "serde_varint")]
+    pub minor: u16,
+    #[serde(with = "serde_varint")]
+    pub patch: u16,
+    pub commit: u32,      // first 4 bytes of the sha1 commit hash
+    pub feature_set: u32, // first 4 bytes of the FeatureSet identifier
+    #[serde(with = "serde_varint")]
+    pub client: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SocketEntry {
+    /// socket tag, e.g. gossip, tvu, tpu, ...
+    pub key: u8,
+    /// index of the ip address inside `ContactInfo::addrs`
+    pub index: u8,
+    /// port offset with respect to the previous entry
+    #[serde(with = "serde_varint")]
+    pub offset: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum Extension {}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ContactInfo {
+    pub pubkey: Pubkey,
+    /// latest wallclock picked
+    #[serde(with = "serde_varint")]
+    pub wallclock: u64,
+    /// when the node instance was first created
+    pub outset: u64,
+    /// node shred version
+    pub shred_version: u16,
+    pub version: ContactInfoVersion,
+    /// unique ip addresses referenced by the sockets
+    #[serde(with = "short_vec")]
+    pub addrs: Vec<IpAddr>,
+    /// sockets sorted by port, each one encoded as offset from the previous
+    #[serde(with = "short_vec")]
+    pub sockets: Vec<SocketEntry>,
+    #[serde(with = "short_vec")]
+    pub extensions: Vec<Extension>,
+}
+
+impl ContactInfo {
+    pub fn new(pubkey: Pubkey, wallclock: u64, shred_version: u16) -> Self {
+        ContactInfo {
+            pubkey,
+            wallclock,
+            outset: wallclock,
+            shred_version,
+            version: ContactInfoVersion::default(),
+            addrs: Vec::new(),
+            sockets: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Resolves the socket entries into (tag, address) pairs.
+    /// Entries referencing an invalid address index are skipped.
+    pub fn resolve_sockets(&self) -> Vec<(u8, SocketAddr)> {
+        let mut port: u16 = 0;
+        let mut resolved = Vec::with_capacity(self.sockets.len());
+
+        for entry in &self.sockets {
+            port = port.saturating_add(entry.offset);
+
+            if let Some(ip) = self.addrs.get(usize::from(entry.index)) {
+                resolved.push((entry.key, SocketAddr::new(*ip, port)));
+            }
+        }
+
+        resolved
+    }
+
+    pub fn socket(&self, key: u8) -> Option<SocketAddr> {
+        self.resolve_sockets()
+            .into_iter()
+            .find(|(tag, _)| *tag == key)
+            .map(|(_, addr)| addr)
+    }
+
+    /// Sets the socket for the given tag, re-encoding addresses and port offsets.
+    pub fn set_socket(&mut self, key: u8, addr: SocketAddr) {
+        let mut entries = self.resolve_sockets();
+        entries.retain(|(tag, _)| *tag != key);
+        entries.push((key, addr));
+        entries.sort_by_key(|(tag, addr)| (addr.port(), *tag));
+
+        let mut addrs: Vec<IpAddr> = Vec::new();
+        let mut sockets = Vec::with_capacity(entries.len());
+        let mut port: u16 = 0;
+
+        for (tag, addr) in entries {
+            let index = addrs
+                .iter()
+                .position(|ip| *ip == addr.ip())
+                .unwrap_or_else(|| {
+                    addrs.push(addr.ip());
+                    addrs.len() - 1
+                });
+
+            let Ok(index) = u8::try_from(index) else {
+                continue;
+            };
+
+            sockets.push(SocketEntry {
+                key: tag,
+                index,
+                offset: addr.port() - port,
+            });
+            port = addr.port();
+        }
+
+        self.addrs = addrs;
+        self.sockets = sockets;
+    }
+
+    pub fn gossip(&self) -> Option<SocketAddr> {
+        self.socket(SOCKET_TAG_GOSSIP)
+    }
+
+    pub fn tvu(&self) -> Option<SocketAddr> {
+        self.socket(SOCKET_TAG_TVU)
+    }
+
+    pub fn tpu(&self) -> Option<SocketAddr> {
+        self.socket(SOCKET_TAG_TPU)
+    }
+
+    pub fn tpu_quic(&self) -> Option<SocketAddr> {
+        self.socket(SOCKET_TAG_TPU_QUIC)
+    }
+
+    pub fn rpc(&self) -> Option<SocketAddr> {
+        self.socket(SOCKET_TAG_RPC)
+    }
+}
+
+impl From<&ContactInfo> for LegacyContactInfo {
+    fn from(info: &ContactInfo) -> Self {
+        let socket = |key| info.socket(key).unwrap_or_else(socketaddr_default);
+
+        LegacyContactInfo {
+            id: info.pubkey,
+            gossip: socket(SOCKET_TAG_GOSSIP),
+            tvu: socket(SOCKET_TAG_TVU),
+            // the legacy tvu_forwards and repair slots have been repurposed as quic sockets
+            tvu_forwards: socket(SOCKET_TAG_TVU_QUIC),
+            repair: socket(SOCKET_TAG_SERVE_REPAIR_QUIC),
+            tpu: socket(SOCKET_TAG_TPU),
+            tpu_forwards: socket(SOCKET_TAG_TPU_FORWARDS),
+            tpu_vote: socket(SOCKET_TAG_TPU_VOTE),
+            rpc: socket(SOCKET_TAG_RPC),
+            rpc_pubsub: socket(SOCKET_TAG_RPC_PUBSUB),
+            serve_repair: socket(SOCKET_TAG_SERVE_REPAIR),
+            wallclock: info.wallclock,
+            shred_version: info.shred_version,
+        }
+    }
+}
+
 pub type VoteIndex = u8;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -81,6 +286,17 @@ pub struct Vote {
     pub(crate) wallclock: u64,
 }
 
+impl Vote {
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// The voted slots, root and hash carried by the vote transaction.
+    pub fn parse(&self) -> Option<ParsedVote> {
+        parse_vote_transaction(&self.transaction)
+    }
+}
+
 pub type Slot = u64;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -90,6 +306,13 @@ pub struct SnapshotHashes {
     pub wallclock: u64,
 }
 
+impl SnapshotHashes {
+    /// The snapshot with the highest slot.
+    pub fn latest(&self) -> Option<(Slot, Hash)> {
+        self.hashes.iter().max_by_key(|(slot, _)| *slot).copied()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct LegacyVersion1 {
     major: u16,
@@ -121,6 +344,79 @@ pub struct Version {
     pub version: LegacyVersion2,
 }
 
+/// The client version advertised by a node, from any of the values carrying it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct NodeVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    /// first 4 bytes of the sha1 commit hash
+    pub commit: Option<u32>,
+    /// first 4 bytes of the `FeatureSet` identifier, missing from `LegacyVersion`
+    pub feature_set: Option<u32>,
+}
+
+impl NodeVersion {
+    pub fn semver(&self) -> String {
+        format!("{}.{}.{}", self.major, self.minor, self.patch)
+    }
+
+    pub fn commit_hex(&self) -> Option<String> {
+        self.commit.map(|commit| format!("{commit:08x}"))
+    }
+
+    pub fn feature_set_hex(&self) -> Option<String> {
+        self.feature_set
+            .map(|feature_set| format!("{feature_set:08x}"))
+    }
+}
+
+impl fmt::Display for NodeVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.semver())?;
+        if let Some(commit) = self.commit_hex() {
+            write!(f, " {commit}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&LegacyVersion1> for NodeVersion {
+    fn from(version: &LegacyVersion1) -> Self {
+        NodeVersion {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            commit: version.commit,
+            feature_set: None,
+        }
+    }
+}
+
+impl From<&LegacyVersion2> for NodeVersion {
+    fn from(version: &LegacyVersion2) -> Self {
+        NodeVersion {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            commit: version.commit,
+            feature_set: Some(version.feature_set),
+        }
+    }
+}
+
+impl From<&ContactInfoVersion> for NodeVersion {
+    fn from(version: &ContactInfoVersion) -> Self {
+        NodeVersion {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            commit: Some(version.commit),
+            feature_set: Some(version.feature_set),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct NodeInstance {
     pub from: Pubkey,
@@ -158,28 +454,123 @@ pub struct EpochSlots {
     pub wallclock: u64,
 }
 
+impl Flate2 {
+    pub fn inflate(&self) -> Result<Uncompressed> {
+        let mut uncompressed = Vec::new();
+        DeflateDecoder::new(&self.compressed[..]).read_to_end(&mut uncompressed)?;
+
+        Ok(Uncompressed {
+            first_slot: self.first_slot,
+            num: self.num,
+            slots: BitVec::from(uncompressed),
+        })
+    }
+}
+
+impl Uncompressed {
+    /// The slots whose bit is set, the bit index is the offset from `first_slot`.
+    /// The slots past `Slot::MAX` are dropped.
+    pub fn to_slots(&self) -> Vec<Slot> {
+        (0..self.slots.len().min(self.num as u64))
+            .filter(|index| self.slots.get(*index))
+            .map_while(|index| self.first_slot.checked_add(index))
+            .collect()
+    }
+}
+
+impl CompressedSlots {
+    pub fn first_slot(&self) -> Slot {
+        match self {
+            CompressedSlots::Flate2(flate2) => flate2.first_slot,
+            CompressedSlots::Uncompressed(uncompressed) => uncompressed.first_slot,
+        }
+    }
+
+    pub fn to_slots(&self) -> Result<Vec<Slot>> {
+        match self {
+            CompressedSlots::Flate2(flate2) => Ok(flate2.inflate()?.to_slots()),
+            CompressedSlots::Uncompressed(uncompressed) => Ok(uncompressed.to_slots()),
+        }
+    }
+}
+
+impl EpochSlots {
+    /// The slots the node claims to have completed, the chunks failing to
+    /// inflate are skipped.
+    pub fn completed_slots(&self) -> impl Iterator<Item = Slot> + '_ {
+        self.slots
+            .iter()
+            .filter_map(|slots| slots.to_slots().ok())
+            .flatten()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
-enum DeprecatedCompressionType {
+pub enum DeprecatedCompressionType {
     Uncompressed,
     GZip,
     BZip2,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
-pub(crate) struct DeprecatedEpochIncompleteSlots {
-    first: Slot,
-    compression: DeprecatedCompressionType,
-    compressed_list: Vec<u8>,
+pub struct DeprecatedEpochIncompleteSlots {
+    pub first: Slot,
+    pub compression: DeprecatedCompressionType,
+    pub compressed_list: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct LowestSlot {
     pub from: Pubkey,
-    root: Slot,
+    /// deprecated, always zero
+    pub root: Slot,
+    /// the lowest slot still available in the node ledger
     pub lowest: Slot,
-    slots: BTreeSet<Slot>,
-    stash: Vec<DeprecatedEpochIncompleteSlots>,
+    /// deprecated, always empty
+    pub slots: BTreeSet<Slot>,
+    /// deprecated, always empty
+    pub stash: Vec<DeprecatedEpochIncompleteSlots>,
+    pub wallclock: u64,
+}
+
+pub type DuplicateShredIndex = u16;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(into = "u8", try_from = "u8")]
+pub enum ShredType {
+    Data = 0b1010_0101,
+    Code = 0b0101_1010,
+}
+
+impl From<ShredType> for u8 {
+    fn from(shred_type: ShredType) -> Self {
+        shred_type as u8
+    }
+}
+
+impl TryFrom<u8> for ShredType {
+    type Error = String;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0b1010_0101 => Ok(ShredType::Data),
+            0b0101_1010 => Ok(ShredType::Code),
+            _ => Err(format!("invalid shred type:{value}")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DuplicateShred {
+    pub from: Pubkey,
     pub wallclock: u64,
+    pub slot: Slot,
+    pub shred_index: u32,
+    pub shred_type: ShredType,
+    /// serialized `DuplicateSlotProof` split into chunks
+    pub num_chunks: u8,
+    pub chunk_index: u8,
+    pub chunk: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -190,6 +581,13 @@ pub struct IncrementalSnapshotHashes {
     pub wallclock: u64,
 }
 
+impl IncrementalSnapshotHashes {
+    /// The incremental snapshot with the highest slot, built on top of `base`.
+    pub fn latest(&self) -> Option<(Slot, Hash)> {
+        self.hashes.iter().max_by_key(|(slot, _)| *slot).copied()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub enum CrdsData {
     LegacyContactInfo(Box<LegacyContactInfo>), // OK len:254
@@ -201,9 +599,9 @@ pub enum CrdsData {
     LegacyVersion(LegacyVersion),              // OK len:163
     Version(Version),                          // OK len:167
     NodeInstance(NodeInstance),                // OK len:168
-    DuplicateShred(),                          // ??
+    DuplicateShred(DuplicateShredIndex, DuplicateShred),
     IncrementalSnapshotHashes(IncrementalSnapshotHashes), // OK len:360
-    ContactInfo(),                             // ??
+    ContactInfo(Box<ContactInfo>),
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -212,12 +610,120 @@ pub struct CrdsValue {
     pub data: CrdsData,
 }
 
+impl CrdsData {
+    /// Pubkey of the node which originated and signed the data.
+    pub fn pubkey(&self) -> Pubkey {
+        match &self {
+            CrdsData::LegacyContactInfo(info) => info.id,
+            CrdsData::Vote(_, vote) => vote.from,
+            CrdsData::LowestSlot(_, slots) => slots.from,
+            CrdsData::SnapshotHashes(hashes) | CrdsData::AccountsHashes(hashes) => hashes.from,
+            CrdsData::EpochSlots(_, slots) => slots.from,
+            CrdsData::LegacyVersion(version) => version.from,
+            CrdsData::Version(version) => version.from,
+            CrdsData::NodeInstance(node) => node.from,
+            CrdsData::DuplicateShred(_, shred) => shred.from,
+            CrdsData::IncrementalSnapshotHashes(hashes) => hashes.from,
+            CrdsData::ContactInfo(info) => info.pubkey,
+        }
+    }
+
+    pub fn wallclock(&self) -> u64 {
+        match &self {
+            CrdsData::LegacyContactInfo(info) => info.wallclock,
+            CrdsData::Vote(_, vote) => vote.wallclock,
+            CrdsData::LowestSlot(_, slots) => slots.wallclock,
+            CrdsData::SnapshotHashes(hashes) | CrdsData::AccountsHashes(hashes) => hashes.wallclock,
+            CrdsData::EpochSlots(_, slots) => slots.wallclock,
+            CrdsData::LegacyVersion(version) => version.wallclock,
+            CrdsData::Version(version) => version.wallclock,
+            CrdsData::NodeInstance(node) => node.wallclock,
+            CrdsData::DuplicateShred(_, shred) => shred.wallclock,
+            CrdsData::IncrementalSnapshotHashes(hashes) => hashes.wallclock,
+            CrdsData::ContactInfo(info) => info.wallclock,
+        }
+    }
+}
+
+/// Key of a value in the crds table, at most one value is kept for each label.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CrdsValueLabel {
+    LegacyContactInfo(Pubkey),
+    Vote(VoteIndex, Pubkey),
+    LowestSlot(Pubkey),
+    SnapshotHashes(Pubkey),
+    AccountsHashes(Pubkey),
+    EpochSlots(EpochSlotsIndex, Pubkey),
+    LegacyVersion(Pubkey),
+    Version(Pubkey),
+    NodeInstance(Pubkey),
+    DuplicateShred(DuplicateShredIndex, Pubkey),
+    IncrementalSnapshotHashes(Pubkey),
+    ContactInfo(Pubkey),
+}
+
+impl CrdsValueLabel {
+    pub fn pubkey(&self) -> Pubkey {
+        match self {
+            CrdsValueLabel::LegacyContactInfo(pubkey)
+            | CrdsValueLabel::Vote(_, pubkey)
+            | CrdsValueLabel::LowestSlot(pubkey)
+            | CrdsValueLabel::SnapshotHashes(pubkey)
+            | CrdsValueLabel::AccountsHashes(pubkey)
+            | CrdsValueLabel::EpochSlots(_, pubkey)
+            | CrdsValueLabel::LegacyVersion(pubkey)
+            | CrdsValueLabel::Version(pubkey)
+            | CrdsValueLabel::NodeInstance(pubkey)
+            | CrdsValueLabel::DuplicateShred(_, pubkey)
+            | CrdsValueLabel::IncrementalSnapshotHashes(pubkey)
+            | CrdsValueLabel::ContactInfo(pubkey) => *pubkey,
+        }
+    }
+}
+
 impl CrdsValue {
     pub fn new_signed(data: CrdsData, keypair: &Keypair) -> Self {
         let signable_data = serialize(&data).expect("failed to serialize CrdsData");
         let signature = keypair.sign_message(&signable_data);
         Self { signature, data }
     }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.data.pubkey()
+    }
+
+    pub fn wallclock(&self) -> u64 {
+        self.data.wallclock()
+    }
+
+    pub fn label(&self) -> CrdsValueLabel {
+        let pubkey = self.pubkey();
+        match &self.data {
+            CrdsData::LegacyContactInfo(_) => CrdsValueLabel::LegacyContactInfo(pubkey),
+            CrdsData::Vote(index, _) => CrdsValueLabel::Vote(*index, pubkey),
+            CrdsData::LowestSlot(_, _) => CrdsValueLabel::LowestSlot(pubkey),
+            CrdsData::SnapshotHashes(_) => CrdsValueLabel::SnapshotHashes(pubkey),
+            CrdsData::AccountsHashes(_) => CrdsValueLabel::AccountsHashes(pubkey),
+            CrdsData::EpochSlots(index, _) => CrdsValueLabel::EpochSlots(*index, pubkey),
+            CrdsData::LegacyVersion(_) => CrdsValueLabel::LegacyVersion(pubkey),
+            CrdsData::Version(_) => CrdsValueLabel::Version(pubkey),
+            CrdsData::NodeInstance(_) => CrdsValueLabel::NodeInstance(pubkey),
+            CrdsData::DuplicateShred(index, _) => CrdsValueLabel::DuplicateShred(*index, pubkey),
+            CrdsData::IncrementalSnapshotHashes(_) => {
+                CrdsValueLabel::IncrementalSnapshotHashes(pubkey)
+            }
+            CrdsData::ContactInfo(_) => CrdsValueLabel::ContactInfo(pubkey),
+        }
+    }
+
+    /// Checks the signature against the bincode of the data and its originator pubkey.
+    pub fn verify(&self) -> bool {
+        let Ok(signable_data) = serialize(&self.data) else {
+            return false;
+        };
+        self.signature
+            .verify(self.pubkey().as_ref(), &signable_data)
+    }
 }
 
 impl fmt::Display for CrdsValue {
@@ -232,9 +738,9 @@ impl fmt::Display for CrdsValue {
             CrdsData::LegacyVersion(_) => write!(f, "LegacyVersion"),
             CrdsData::Version(_) => write!(f, "Version"),
             CrdsData::NodeInstance(_) => write!(f, "NodeInstance"),
-            CrdsData::DuplicateShred() => write!(f, "DuplicateShred"),
+            CrdsData::DuplicateShred(_, _) => write!(f, "DuplicateShred"),
             CrdsData::IncrementalSnapshotHashes(_) => write!(f, "IncrementalSnapshotHashes"),
-            CrdsData::ContactInfo() => write!(f, "ContactInfo"),
+            CrdsData::ContactInfo(_) => write!(f, "ContactInfo"),
         }
     }
 }
@@ -246,37 +752,141 @@ pub struct CrdsFilter {
     pub mask_bits: u32,
 }
 
+const CRDS_FILTER_FALSE_RATE: f64 = 0.1f64;
+const CRDS_FILTER_KEYS: f64 = 8f64;
+/// max size of the bloom filter fitting in a pull request packet
+pub const CRDS_FILTER_MAX_BYTES: usize = 928;
+
 impl Default for CrdsFilter {
     fn default() -> Self {
-        fn compute_mask(seed: u64, mask_bits: u32) -> u64 {
-            assert!(seed <= 2u64.pow(mask_bits));
-            let seed: u64 = seed.checked_shl(64 - mask_bits).unwrap_or(0x0);
-            seed | (!0u64).checked_shr(mask_bits).unwrap_or(!0x0)
-        }
-
-        #[allow(clippy::cast_possible_truncation)]
-        #[allow(clippy::cast_sign_loss)]
-        fn mask_bits(num_items: f64, max_items: f64) -> u32 {
-            // for small ratios this can result in a negative number, ensure it returns 0 instead
-            ((num_items / max_items).log2().ceil()).max(0.0) as u32
-        }
-
         let max_items: u32 = 1287;
         let num_items: u32 = 512;
-        let false_rate: f64 = 0.1f64;
         let max_bits = 7424u32;
-        let mask_bits = mask_bits(f64::from(num_items), f64::from(max_items));
+        let mask_bits = CrdsFilter::mask_bits(f64::from(num_items), f64::from(max_items));
 
-        let bloom: Bloom<Hash> = Bloom::random(max_items as usize, false_rate, max_bits as usize);
+        let bloom: Bloom<Hash> = Bloom::random(
+            max_items as usize,
+            CRDS_FILTER_FALSE_RATE,
+            max_bits as usize,
+        );
 
         CrdsFilter {
             filter: bloom,
-            mask: compute_mask(0_u64, mask_bits),
+            mask: CrdsFilter::compute_mask(0_u64, mask_bits),
             mask_bits,
         }
     }
 }
 
+impl CrdsFilter {
+    fn compute_mask(seed: u64, mask_bits: u32) -> u64 {
+        assert!(seed <= 2u64.pow(mask_bits));
+        let seed: u64 = seed.checked_shl(64 - mask_bits).unwrap_or(0x0);
+        seed | (!0u64).checked_shr(mask_bits).unwrap_or(!0x0)
+    }
+
+    fn max_items(max_bits: f64, false_rate: f64, num_keys: f64) -> f64 {
+        let m = max_bits;
+        let p = false_rate;
+        let k = num_keys;
+        (m / (-k / (1f64 - (p.ln() / k).exp()).ln())).ceil()
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn mask_bits(num_items: f64, max_items: f64) -> u32 {
+        // for small ratios this can result in a negative number, ensure it returns 0 instead
+        ((num_items / max_items).log2().ceil()).max(0.0) as u32
+    }
+
+    pub fn hash_as_u64(item: &Hash) -> u64 {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&item.as_ref()[..8]);
+        u64::from_le_bytes(buf)
+    }
+
+    /// True if the highest `mask_bits` bits of the hash match the filter mask.
+    pub fn test_mask(&self, item: &Hash) -> bool {
+        let ones = (!0u64).checked_shr(self.mask_bits).unwrap_or(!0u64);
+        let bits = CrdsFilter::hash_as_u64(item) | ones;
+        bits == self.mask
+    }
+
+    /// True if the value with the given hash is not requested by the filter.
+    pub fn contains(&self, item: &Hash) -> bool {
+        if !self.test_mask(item) {
+            return true;
+        }
+        self.filter.contains(item)
+    }
+}
+
+/// Bloom filters of the hashes held locally, split by the highest `mask_bits`
+/// bits of the hash so that each filter fits in a pull request.
+#[derive(Debug)]
+pub struct CrdsFilterSet {
+    filters: Vec<Bloom<Hash>>,
+    mask_bits: u32,
+}
+
+impl CrdsFilterSet {
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn new(num_items: usize, max_bytes: usize) -> Self {
+        let max_bits = (max_bytes * 8) as f64;
+        let max_items = CrdsFilter::max_items(max_bits, CRDS_FILTER_FALSE_RATE, CRDS_FILTER_KEYS);
+        let mask_bits = CrdsFilter::mask_bits(num_items as f64, max_items);
+
+        let filters = (0..1usize << mask_bits)
+            .map(|_| {
+                Bloom::random(
+                    max_items as usize,
+                    CRDS_FILTER_FALSE_RATE,
+                    max_bits as usize,
+                )
+            })
+            .collect();
+
+        Self { filters, mask_bits }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn add(&mut self, hash: &Hash) {
+        let index = CrdsFilter::hash_as_u64(hash)
+            .checked_shr(64 - self.mask_bits)
+            .unwrap_or(0);
+        self.filters[index as usize].add(hash);
+    }
+
+    pub fn mask_bits(&self) -> u32 {
+        self.mask_bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+}
+
+impl From<CrdsFilterSet> for Vec<CrdsFilter> {
+    fn from(set: CrdsFilterSet) -> Self {
+        let mask_bits = set.mask_bits;
+        set.filters
+            .into_iter()
+            .zip(0u64..)
+            .map(|(filter, seed)| CrdsFilter {
+                filter,
+                mask: CrdsFilter::compute_mask(seed, mask_bits),
+                mask_bits,
+            })
+            .collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct PingGeneric<T> {
     pub from: Pubkey,
@@ -289,9 +899,35 @@ const GOSSIP_PING_TOKEN_SIZE: usize = 32;
 
 pub type Ping = PingGeneric<[u8; GOSSIP_PING_TOKEN_SIZE]>;
 
+impl<T: SerdeSerialize> PingGeneric<T> {
+    pub fn new(token: T, keypair: &Keypair) -> Result<Self> {
+        let signature = keypair.sign_message(&serialize(&token)?);
+        Ok(PingGeneric {
+            from: keypair.pubkey(),
+            token,
+            signature,
+        })
+    }
+
+    pub fn token(&self) -> &T {
+        &self.token
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn verify(&self) -> bool {
+        let Ok(token) = serialize(&self.token) else {
+            return false;
+        };
+        self.signature.verify(self.from.as_ref(), &token)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Pong {
-    from: Pubkey,
+    pub from: Pubkey,
     hash: Hash, // Hash of received ping token.
     signature: Signature,
 }
@@ -309,6 +945,100 @@ impl Pong {
         };
         Ok(pong_response)
     }
+
+    /// The hash of the ping token answered.
+    pub fn hash(&self) -> &Hash {
+        &self.hash
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn verify(&self) -> bool {
+        self.signature
+            .verify(self.from.as_ref(), self.hash.as_ref())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PruneData {
+    /// pubkey of the node that sent this prune data
+    pub pubkey: Pubkey,
+    /// pubkeys of the origins that should be pruned
+    pub prunes: Vec<Pubkey>,
+    pub signature: Signature,
+    /// pubkey of the intended destination of this message
+    pub destination: Pubkey,
+    pub wallclock: u64,
+}
+
+const PRUNE_DATA_PREFIX: &[u8] = b"\xffSOLANA_PRUNE_DATA";
+
+#[derive(Serialize)]
+struct PruneSignData<'a> {
+    pubkey: &'a Pubkey,
+    prunes: &'a [Pubkey],
+    destination: &'a Pubkey,
+    wallclock: u64,
+}
+
+#[derive(Serialize)]
+struct PruneSignDataWithPrefix<'a> {
+    prefix: &'a [u8],
+    pubkey: &'a Pubkey,
+    prunes: &'a [Pubkey],
+    destination: &'a Pubkey,
+    wallclock: u64,
+}
+
+impl PruneData {
+    pub fn new_signed(
+        keypair: &Keypair,
+        prunes: Vec<Pubkey>,
+        destination: Pubkey,
+        wallclock: u64,
+    ) -> Result<Self> {
+        let mut prune_data = PruneData {
+            pubkey: keypair.pubkey(),
+            prunes,
+            signature: Signature::default(),
+            destination,
+            wallclock,
+        };
+        prune_data.signature = keypair.sign_message(&prune_data.signable_data(true)?);
+        Ok(prune_data)
+    }
+
+    /// The data is signed prefixed by a domain separator, older nodes sign it without.
+    fn signable_data(&self, use_prefix: bool) -> Result<Vec<u8>> {
+        let data = if use_prefix {
+            serialize(&PruneSignDataWithPrefix {
+                prefix: PRUNE_DATA_PREFIX,
+                pubkey: &self.pubkey,
+                prunes: &self.prunes,
+                destination: &self.destination,
+                wallclock: self.wallclock,
+            })?
+        } else {
+            serialize(&PruneSignData {
+                pubkey: &self.pubkey,
+                prunes: &self.prunes,
+                destination: &self.destination,
+                wallclock: self.wallclock,
+            })?
+        };
+        Ok(data)
+    }
+
+    pub fn verify(&self) -> bool {
+        [true, false]
+            .into_iter()
+            .any(|use_prefix| match self.signable_data(use_prefix) {
+                Ok(data) => self.signature.verify(self.pubkey.as_ref(), &data),
+                Err(_) => false,
+            })
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -316,7 +1046,7 @@ pub enum Protocol {
     PullRequest(CrdsFilter, CrdsValue),
     PullResponse(Pubkey, Vec<CrdsValue>),
     PushMessage(Pubkey, Vec<CrdsValue>),
-    PruneMessage(Pubkey),
+    PruneMessage(Pubkey, PruneData),
     PingMessage(Ping),
     PongMessage(Pong),
 }
@@ -327,7 +1057,7 @@ impl fmt::Display for Protocol {
             Protocol::PullRequest(_, _) => write!(f, "PullRequest"),
             Protocol::PullResponse(_, _) => write!(f, "PullResponse"),
             Protocol::PushMessage(_, _) => write!(f, "PushMessage"),
-            Protocol::PruneMessage(_) => write!(f, "PruneMessage"),
+            Protocol::PruneMessage(_, _) => write!(f, "PruneMessage"),
             Protocol::PingMessage(_) => write!(f, "PingMessage"),
             Protocol::PongMessage(_) => write!(f, "PongMessage"),
         }
@@ -338,8 +1068,12 @@ impl fmt::Display for Protocol {
 #[cfg(test)]
 mod tests {
 
+    use std::io::Write;
+
+    use flate2::{write::DeflateEncoder, Compression};
+
     use crate::{
-        utils::parse_addr,
+        utils::{create_prune_message, create_pull_request, create_push_message, parse_addr},
         wire::{Payload, PACKET_DATA_SIZE},
     };
 
@@ -360,6 +1094,137 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_verify_crds_value() {
+        let keypair = Keypair::new();
+
+        let contact_info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            ..LegacyContactInfo::default()
+        };
+        let crds_data = CrdsData::LegacyContactInfo(Box::new(contact_info));
+        let crds_value = CrdsValue::new_signed(crds_data, &keypair);
+
+        assert_eq!(crds_value.pubkey(), keypair.pubkey());
+        assert!(crds_value.verify());
+
+        // tampered data
+        let mut tampered = crds_value.clone();
+        if let CrdsData::LegacyContactInfo(info) = &mut tampered.data {
+            info.wallclock += 1;
+        }
+        assert!(!tampered.verify());
+
+        // signed by a key different from the data originator
+        let spoofed = CrdsValue::new_signed(crds_value.data, &Keypair::new());
+        assert!(!spoofed.verify());
+    }
+
+    #[test]
+    fn test_verify_ping_pong() {
+        let keypair = Keypair::new();
+
+        let ping = Ping::new([7u8; GOSSIP_PING_TOKEN_SIZE], &keypair).unwrap();
+        assert_eq!(ping.from, keypair.pubkey());
+        assert!(ping.verify());
+
+        let mut tampered = ping.clone();
+        tampered.token[0] = 8;
+        assert!(!tampered.verify());
+
+        let pong_response = Pong::new(&ping, &Keypair::new()).unwrap();
+        assert!(pong_response.verify());
+
+        let mut spoofed = pong_response.clone();
+        spoofed.from = keypair.pubkey();
+        assert!(!spoofed.verify());
+    }
+
+    #[test]
+    fn test_verify_prune_data() {
+        let keypair = Keypair::new();
+        let prunes = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let destination = Pubkey::new_unique();
+
+        let prune_data =
+            PruneData::new_signed(&keypair, prunes.clone(), destination, 1_681_747_487_616)
+                .unwrap();
+        assert!(prune_data.verify());
+
+        // signed without the domain prefix by older nodes
+        let mut legacy_prune_data = prune_data.clone();
+        legacy_prune_data.signature =
+            keypair.sign_message(&legacy_prune_data.signable_data(false).unwrap());
+        assert_ne!(legacy_prune_data.signature, prune_data.signature);
+        assert!(legacy_prune_data.verify());
+
+        let mut tampered = prune_data.clone();
+        tampered.prunes.pop();
+        assert!(!tampered.verify());
+
+        let mut spoofed = prune_data;
+        spoofed.pubkey = destination;
+        assert!(!spoofed.verify());
+    }
+
+    #[test]
+    fn test_parse_prune_message() {
+        let keypair = Keypair::new();
+        let prunes = vec![Pubkey::new_unique()];
+        let destination = Pubkey::new_unique();
+
+        let payload = create_prune_message(
+            prunes.clone(),
+            destination,
+            &keypair,
+            parse_addr("10.20.30.40:9000").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(payload.len, 4 + 32 + 32 + 8 + 32 + 64 + 32 + 8);
+
+        let protocol: Protocol = payload.deserialize_slice(..).unwrap();
+        assert!(matches!(protocol, Protocol::PruneMessage(_, _)));
+
+        if let Protocol::PruneMessage(from, prune_data) = protocol {
+            assert_eq!(from, keypair.pubkey());
+            assert_eq!(prune_data.pubkey, keypair.pubkey());
+            assert_eq!(prune_data.prunes, prunes);
+            assert_eq!(prune_data.destination, destination);
+            assert!(prune_data.verify());
+        }
+    }
+
+    #[test]
+    fn test_parse_push_message() {
+        let keypair = Keypair::new();
+
+        let contact_info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            gossip: parse_addr("10.20.30.40:9000").unwrap(),
+            ..LegacyContactInfo::default()
+        };
+        let crds_value = CrdsValue::new_signed(
+            CrdsData::LegacyContactInfo(Box::new(contact_info)),
+            &keypair,
+        );
+
+        let payload = create_push_message(
+            vec![crds_value.clone()],
+            &keypair,
+            parse_addr("10.20.30.40:9000").unwrap(),
+        )
+        .unwrap();
+
+        let protocol: Protocol = payload.deserialize_slice(..).unwrap();
+        assert!(matches!(protocol, Protocol::PushMessage(_, _)));
+
+        if let Protocol::PushMessage(from, crds_values) = protocol {
+            assert_eq!(from, keypair.pubkey());
+            assert_eq!(crds_values, vec![crds_value]);
+            assert!(crds_values[0].verify());
+        }
+    }
+
     #[test]
     fn test_crds_filter() {
         let crds_filter = CrdsFilter::default();
@@ -367,6 +1232,48 @@ mod tests {
         assert_eq!(crds_filter.filter.bits.len(), 6168);
     }
 
+    #[test]
+    fn test_crds_filter_set() {
+        assert_eq!(CrdsFilterSet::new(0, CRDS_FILTER_MAX_BYTES).len(), 1);
+        assert_eq!(CrdsFilterSet::new(1287, CRDS_FILTER_MAX_BYTES).len(), 1);
+        assert_eq!(CrdsFilterSet::new(1288, CRDS_FILTER_MAX_BYTES).len(), 2);
+
+        let hashes = (0..10_000).map(|_| Hash::new_unique()).collect::<Vec<_>>();
+        let mut filter_set = CrdsFilterSet::new(hashes.len(), CRDS_FILTER_MAX_BYTES);
+        assert_eq!(filter_set.mask_bits(), 3);
+        assert_eq!(filter_set.len(), 8);
+
+        for hash in &hashes {
+            filter_set.add(hash);
+        }
+
+        let filters: Vec<CrdsFilter> = filter_set.into();
+        let masks = filters
+            .iter()
+            .map(|filter| filter.mask)
+            .collect::<BTreeSet<_>>();
+        assert_eq!(masks.len(), 8);
+
+        // each hash is covered by exactly one filter
+        for hash in &hashes {
+            let matching = filters
+                .iter()
+                .filter(|filter| filter.test_mask(hash))
+                .collect::<Vec<_>>();
+            assert_eq!(matching.len(), 1);
+            assert!(matching[0].filter.contains(hash));
+        }
+
+        // the filters still fit in a pull request packet
+        let payload = create_pull_request(
+            LegacyContactInfo::default(),
+            filters[0].clone(),
+            &Keypair::new(),
+            parse_addr("10.20.30.40:9000").unwrap(),
+        );
+        assert!(payload.is_ok());
+    }
+
     fn create_payload(message: &[u8]) -> Payload {
         let mut buf = [0; PACKET_DATA_SIZE];
 
@@ -440,6 +1347,7 @@ mod tests {
         assert!(matches!(protocol, Protocol::PongMessage(_)));
 
         if let Protocol::PongMessage(pong) = protocol {
+            assert!(pong.verify());
 
             assert_eq!(
                 pong.from.to_string(),
@@ -593,4 +1501,224 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_parse_pull_response_contact_info_message() {
+        let data: [u8; 207] = [
+            1, 0, 0, 0, 112, 26, 219, 83, 31, 191, 215, 27, 61, 28, 154, 238, 134, 84, 53, 138,
+            195, 64, 71, 69, 95, 125, 193, 73, 179, 255, 150, 187, 36, 104, 203, 159, 1, 0, 0, 0,
+            0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+            22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43,
+            44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 11,
+            0, 0, 0, 112, 26, 219, 83, 31, 191, 215, 27, 61, 28, 154, 238, 134, 84, 53, 138, 195,
+            64, 71, 69, 95, 125, 193, 73, 179, 255, 150, 187, 36, 104, 203, 159, 128, 215, 216,
+            255, 248, 48, 0, 235, 131, 143, 135, 1, 0, 0, 247, 1, 1, 18, 11, 221, 204, 187, 170,
+            78, 109, 67, 251, 3, 2, 0, 0, 0, 0, 10, 20, 30, 40, 0, 0, 0, 0, 127, 0, 0, 1, 5, 2, 1,
+            195, 69, 0, 0, 101, 10, 0, 1, 5, 0, 3, 8, 0, 6, 0,
+        ];
+
+        let info_payload = create_payload(&data);
+
+        let protocol: Protocol = info_payload.deserialize_slice(..).unwrap();
+
+        assert!(matches!(protocol, Protocol::PullResponse(_, _)));
+
+        if let Protocol::PullResponse(_, crds_values) = &protocol {
+            let crds_data = &crds_values[0].data;
+            assert!(matches!(crds_data, CrdsData::ContactInfo(_,)));
+
+            if let CrdsData::ContactInfo(info) = crds_data {
+                assert_eq!(
+                    info.pubkey.to_string(),
+                    "8YcR2zEgUXYkKBtnWCSWM3Hbycu6RMqNvi9sGJmvezQE"
+                );
+                assert_eq!(info.wallclock, 1_681_747_487_616);
+                assert_eq!(info.outset, 1_681_740_000_000);
+                assert_eq!(info.shred_version, 503);
+                assert_eq!(info.version.major, 1);
+                assert_eq!(info.version.minor, 18);
+                assert_eq!(info.version.patch, 11);
+                assert_eq!(info.version.commit, 0xaabb_ccdd);
+                assert_eq!(info.version.feature_set, 4_215_500_110);
+                assert_eq!(info.version.client, 3);
+                assert_eq!(info.addrs.len(), 2);
+                assert_eq!(info.sockets.len(), 5);
+
+                assert_eq!(info.gossip(), parse_addr("10.20.30.40:9000"));
+                assert_eq!(info.tvu(), parse_addr("10.20.30.40:9001"));
+                assert_eq!(info.tpu(), parse_addr("10.20.30.40:9004"));
+                assert_eq!(info.tpu_quic(), parse_addr("10.20.30.40:9010"));
+                assert_eq!(info.rpc(), parse_addr("127.0.0.1:8899"));
+                assert_eq!(info.socket(SOCKET_TAG_TPU_VOTE), None);
+
+                let legacy = LegacyContactInfo::from(info.as_ref());
+                assert_eq!(legacy.id, info.pubkey);
+                assert_eq!(legacy.gossip, parse_addr("10.20.30.40:9000").unwrap());
+                assert_eq!(legacy.tpu_vote, parse_addr("0.0.0.0:0").unwrap());
+                assert_eq!(legacy.shred_version, 503);
+            }
+        }
+
+        let mut payload = Payload::default();
+        payload.populate_packet(None, &protocol).unwrap();
+        assert_eq!(payload.data(..), Some(&data[..]));
+    }
+
+    #[test]
+    fn test_contact_info_set_socket() {
+        let mut info = ContactInfo::new(Pubkey::new_unique(), 1_681_747_487_616, 0);
+
+        info.set_socket(SOCKET_TAG_TPU, parse_addr("10.20.30.40:9004").unwrap());
+        info.set_socket(SOCKET_TAG_GOSSIP, parse_addr("10.20.30.40:9000").unwrap());
+        info.set_socket(SOCKET_TAG_RPC, parse_addr("127.0.0.1:8899").unwrap());
+        info.set_socket(SOCKET_TAG_TPU, parse_addr("10.20.30.40:9005").unwrap());
+
+        assert_eq!(info.addrs.len(), 2);
+        assert_eq!(
+            info.sockets,
+            vec![
+                SocketEntry {
+                    key: SOCKET_TAG_RPC,
+                    index: 0,
+                    offset: 8899
+                },
+                SocketEntry {
+                    key: SOCKET_TAG_GOSSIP,
+                    index: 1,
+                    offset: 101
+                },
+                SocketEntry {
+                    key: SOCKET_TAG_TPU,
+                    index: 1,
+                    offset: 5
+                },
+            ]
+        );
+
+        let crds_value = CrdsValue::new_signed(
+            CrdsData::ContactInfo(Box::new(info.clone())),
+            &Keypair::new(),
+        );
+        let bytes = serialize(&crds_value).unwrap();
+        let decoded: CrdsValue = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(decoded, crds_value);
+        if let CrdsData::ContactInfo(decoded_info) = decoded.data {
+            assert_eq!(decoded_info.tpu(), parse_addr("10.20.30.40:9005"));
+            assert_eq!(decoded_info.resolve_sockets(), info.resolve_sockets());
+        }
+    }
+
+    #[test]
+    fn test_epoch_slots_completed_slots() {
+        let mut bits = BitVec::new_fill(false, 16);
+        bits.set(0, true);
+        bits.set(3, true);
+        bits.set(9, true);
+        let uncompressed = Uncompressed {
+            first_slot: 1000,
+            num: 8,
+            slots: bits,
+        };
+        // the bits after num are ignored
+        assert_eq!(uncompressed.to_slots(), vec![1000, 1003]);
+
+        let mut bits = BitVec::new_fill(false, 16);
+        bits.set(0, true);
+        bits.set(2, true);
+        let overflowing = Uncompressed {
+            first_slot: u64::MAX - 1,
+            num: 16,
+            slots: bits,
+        };
+        // the slots past the max are dropped
+        assert_eq!(overflowing.to_slots(), vec![u64::MAX - 1]);
+
+        let mut bits = BitVec::new_fill(false, 16);
+        bits.set(1, true);
+        bits.set(15, true);
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&bits.into_boxed_slice()).unwrap();
+        let flate2 = Flate2 {
+            first_slot: 2000,
+            num: 16,
+            compressed: encoder.finish().unwrap(),
+        };
+        assert_eq!(flate2.inflate().unwrap().to_slots(), vec![2001, 2015]);
+
+        let invalid = Flate2 {
+            first_slot: 3000,
+            num: 16,
+            compressed: vec![0xff; 4],
+        };
+        assert!(CompressedSlots::Flate2(invalid.clone()).to_slots().is_err());
+
+        let epoch_slots = EpochSlots {
+            from: Pubkey::new_unique(),
+            slots: vec![
+                CompressedSlots::Uncompressed(uncompressed),
+                CompressedSlots::Flate2(invalid),
+                CompressedSlots::Flate2(flate2),
+            ],
+            wallclock: 0,
+        };
+        assert_eq!(
+            epoch_slots.completed_slots().collect::<Vec<_>>(),
+            vec![1000, 1003, 2001, 2015]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_hashes_latest() {
+        let (hash1, hash2) = (Hash::new_unique(), Hash::new_unique());
+
+        let full = SnapshotHashes {
+            from: Pubkey::new_unique(),
+            hashes: vec![(300, hash1), (500, hash2), (400, hash1)],
+            wallclock: 0,
+        };
+        assert_eq!(full.latest(), Some((500, hash2)));
+
+        let incremental = IncrementalSnapshotHashes {
+            from: Pubkey::new_unique(),
+            base: (500, hash2),
+            hashes: vec![],
+            wallclock: 0,
+        };
+        assert_eq!(incremental.latest(), None);
+    }
+
+    #[test]
+    fn test_node_version() {
+        let legacy = NodeVersion::from(&LegacyVersion1 {
+            major: 1,
+            minor: 14,
+            patch: 20,
+            commit: Some(0x0a1b_2c3d),
+        });
+        assert_eq!(legacy.to_string(), "1.14.20 0a1b2c3d");
+        assert_eq!(legacy.feature_set_hex(), None);
+
+        let version = NodeVersion::from(&LegacyVersion2 {
+            major: 1,
+            minor: 18,
+            patch: 2,
+            commit: None,
+            feature_set: 0xdead_beef,
+        });
+        assert_eq!(version.to_string(), "1.18.2");
+        assert_eq!(version.commit_hex(), None);
+        assert_eq!(version.feature_set_hex(), Some("deadbeef".to_string()));
+
+        let version = NodeVersion::from(&ContactInfoVersion {
+            major: 1,
+            minor: 18,
+            patch: 3,
+            commit: 0xff,
+            feature_set: 1,
+            client: 0,
+        });
+        assert_eq!(version.to_string(), "1.18.3 000000ff");
+        assert!(legacy < version);
+    }
 }
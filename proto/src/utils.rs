@@ -1,12 +1,20 @@
 use std::{
+    fs,
     net::{SocketAddr, ToSocketAddrs},
+    path::Path,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use solana_sdk::signature::Keypair;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, write_keypair_file, Keypair},
+    signer::Signer,
+};
 
-use crate::errors::Result;
-use crate::protocol::{CrdsData, CrdsFilter, CrdsValue, LegacyContactInfo, Ping, Pong, Protocol};
+use crate::errors::{Error, Result};
+use crate::protocol::{
+    CrdsData, CrdsFilter, CrdsValue, LegacyContactInfo, Ping, Pong, Protocol, PruneData,
+};
 use crate::wire::Payload;
 
 #[allow(clippy::cast_possible_truncation)]
@@ -27,14 +35,33 @@ pub fn parse_addr(addr: &str) -> Option<SocketAddr> {
     addrs.first().copied()
 }
 
+/// Reads the node identity from a Solana CLI JSON keypair file, on first run a
+/// new keypair is generated and saved to the given path.
+pub fn load_or_create_keypair<P: AsRef<Path>>(path: P) -> Result<Keypair> {
+    let path = path.as_ref();
+
+    if path.exists() {
+        return read_keypair_file(path).map_err(|err| Error::KeypairError(err.to_string()));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let keypair = Keypair::new();
+    write_keypair_file(&keypair, path).map_err(|err| Error::KeypairError(err.to_string()))?;
+
+    Ok(keypair)
+}
+
 pub fn create_pull_request(
     contact_info: LegacyContactInfo,
+    crds_filter: CrdsFilter,
     keypair: &Keypair,
     entrypoint_addr: SocketAddr,
 ) -> Result<Payload> {
     let crds_data = CrdsData::LegacyContactInfo(Box::new(contact_info));
     let crds_value = CrdsValue::new_signed(crds_data, keypair);
-    let crds_filter = CrdsFilter::default();
 
     let protocol = Protocol::PullRequest(crds_filter, crds_value);
 
@@ -58,6 +85,34 @@ pub fn create_pong_response(
     Ok(payload)
 }
 
+pub fn create_push_message(
+    values: Vec<CrdsValue>,
+    keypair: &Keypair,
+    dest_addr: SocketAddr,
+) -> Result<Payload> {
+    let protocol = Protocol::PushMessage(keypair.pubkey(), values);
+
+    let mut payload = Payload::default();
+    payload.populate_packet(Some(dest_addr), &protocol)?;
+
+    Ok(payload)
+}
+
+pub fn create_prune_message(
+    prunes: Vec<Pubkey>,
+    destination: Pubkey,
+    keypair: &Keypair,
+    dest_addr: SocketAddr,
+) -> Result<Payload> {
+    let prune_data = PruneData::new_signed(keypair, prunes, destination, since_the_epoch_millis())?;
+    let protocol = Protocol::PruneMessage(keypair.pubkey(), prune_data);
+
+    let mut payload = Payload::default();
+    payload.populate_packet(Some(dest_addr), &protocol)?;
+
+    Ok(payload)
+}
+
 //tests
 #[cfg(test)]
 mod tests {
@@ -104,4 +159,26 @@ mod tests {
     fn test_parse_addr_invalid() {
         assert_eq!(parse_addr("host,8000"), None);
     }
+
+    #[test]
+    fn test_load_or_create_keypair() {
+        let dir = std::env::temp_dir().join(format!("gossip-id-{}", Pubkey::new_unique()));
+        let path = dir.join("id.json");
+
+        let keypair = load_or_create_keypair(&path).unwrap();
+        assert!(path.exists());
+
+        // Solana CLI format, a JSON array of the 64 keypair bytes
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.starts_with('[') && json.ends_with(']'));
+        assert_eq!(json.split(',').count(), 64);
+
+        let loaded = load_or_create_keypair(&path).unwrap();
+        assert_eq!(loaded.pubkey(), keypair.pubkey());
+
+        fs::write(&path, "[1,2,3]").unwrap();
+        assert!(load_or_create_keypair(&path).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -0,0 +1,281 @@
+use bincode::Options;
+use serde_derive::{Deserialize, Serialize};
+use solana_sdk::{
+    clock::UnixTimestamp,
+    hash::Hash,
+    pubkey::Pubkey,
+    serde_varint, short_vec,
+    transaction::Transaction,
+    vote::{self, instruction::VoteInstruction},
+};
+
+use crate::{protocol::Slot, wire::PACKET_DATA_SIZE};
+
+// instruction tags missing from the sdk `VoteInstruction`
+const TOWER_SYNC: u32 = 14;
+const TOWER_SYNC_SWITCH: u32 = 15;
+
+/// The tower carried by a vote transaction.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParsedVote {
+    pub vote_account: Pubkey,
+    /// voted slots, from the oldest one
+    pub slots: Vec<Slot>,
+    pub root: Option<Slot>,
+    /// bank hash of the last voted slot
+    pub hash: Hash,
+    pub timestamp: Option<UnixTimestamp>,
+    /// the proof hash of a fork switching vote
+    pub switch_proof_hash: Option<Hash>,
+}
+
+impl ParsedVote {
+    pub fn last_voted_slot(&self) -> Option<Slot> {
+        self.slots.last().copied()
+    }
+}
+
+// `TowerSync` as serialized by the vote program: slots are offsets from the
+// previous one, starting from the root
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+struct CompactTowerSync {
+    root: Slot,
+    #[serde(with = "short_vec")]
+    lockout_offsets: Vec<LockoutOffset>,
+    hash: Hash,
+    timestamp: Option<UnixTimestamp>,
+    block_id: Hash,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+struct LockoutOffset {
+    #[serde(with = "serde_varint")]
+    offset: Slot,
+    confirmation_count: u8,
+}
+
+impl CompactTowerSync {
+    fn slots(&self) -> Option<Vec<Slot>> {
+        let mut slot = if self.root == Slot::MAX { 0 } else { self.root };
+        self.lockout_offsets
+            .iter()
+            .map(|lockout| {
+                slot = slot.checked_add(lockout.offset)?;
+                Some(slot)
+            })
+            .collect()
+    }
+}
+
+/// Parses the vote instruction of a gossip vote transaction, `None` if the
+/// first instruction is not a vote one.
+pub fn parse_vote_transaction(tx: &Transaction) -> Option<ParsedVote> {
+    let message = &tx.message;
+    let instruction = message.instructions.first()?;
+    let program_id = message
+        .account_keys
+        .get(usize::from(instruction.program_id_index))?;
+    if !vote::program::check_id(program_id) {
+        return None;
+    }
+    let vote_account = *message
+        .account_keys
+        .get(usize::from(*instruction.accounts.first()?))?;
+
+    let data = &instruction.data;
+    let tag = u32::from_le_bytes(data.get(..4)?.try_into().ok()?);
+
+    if tag == TOWER_SYNC || tag == TOWER_SYNC_SWITCH {
+        let (tower, switch_proof_hash) = if tag == TOWER_SYNC {
+            (deserialize::<CompactTowerSync>(&data[4..])?, None)
+        } else {
+            let (tower, hash) = deserialize::<(CompactTowerSync, Hash)>(&data[4..])?;
+            (tower, Some(hash))
+        };
+
+        return Some(ParsedVote {
+            vote_account,
+            slots: tower.slots()?,
+            root: (tower.root != Slot::MAX).then_some(tower.root),
+            hash: tower.hash,
+            timestamp: tower.timestamp,
+            switch_proof_hash,
+        });
+    }
+
+    let (slots, root, hash, timestamp, switch_proof_hash) = match deserialize(data)? {
+        VoteInstruction::Vote(vote) => (vote.slots, None, vote.hash, vote.timestamp, None),
+        VoteInstruction::VoteSwitch(vote, switch) => {
+            (vote.slots, None, vote.hash, vote.timestamp, Some(switch))
+        }
+        VoteInstruction::UpdateVoteState(update)
+        | VoteInstruction::CompactUpdateVoteState(update) => (
+            update.slots(),
+            update.root,
+            update.hash,
+            update.timestamp,
+            None,
+        ),
+        VoteInstruction::UpdateVoteStateSwitch(update, switch)
+        | VoteInstruction::CompactUpdateVoteStateSwitch(update, switch) => (
+            update.slots(),
+            update.root,
+            update.hash,
+            update.timestamp,
+            Some(switch),
+        ),
+        _ => return None,
+    };
+
+    Some(ParsedVote {
+        vote_account,
+        slots,
+        root,
+        hash,
+        timestamp,
+        switch_proof_hash,
+    })
+}
+
+// as the vote program does, trailing bytes are allowed
+fn deserialize<T: serde::de::DeserializeOwned>(data: &[u8]) -> Option<T> {
+    bincode::options()
+        .with_limit(PACKET_DATA_SIZE as u64)
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .deserialize(data)
+        .ok()
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        signature::Keypair,
+        signer::Signer,
+        vote::{
+            instruction as vote_instruction,
+            state::{Vote, VoteStateUpdate},
+        },
+    };
+
+    use super::*;
+
+    fn new_vote_transaction(instruction: Instruction, keypair: &Keypair) -> Transaction {
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&keypair.pubkey()));
+        tx.partial_sign(&[keypair], Hash::default());
+        tx
+    }
+
+    #[test]
+    fn test_parse_vote() {
+        let keypair = Keypair::new();
+        let vote_account = Pubkey::new_unique();
+        let hash = Hash::new_unique();
+
+        let vote = Vote {
+            slots: vec![10, 11, 12],
+            hash,
+            timestamp: Some(1_700_000_000),
+        };
+        let instruction = vote_instruction::vote(&vote_account, &keypair.pubkey(), vote);
+        let tx = new_vote_transaction(instruction, &keypair);
+
+        let parsed = parse_vote_transaction(&tx).unwrap();
+        assert_eq!(parsed.vote_account, vote_account);
+        assert_eq!(parsed.slots, vec![10, 11, 12]);
+        assert_eq!(parsed.last_voted_slot(), Some(12));
+        assert_eq!(parsed.root, None);
+        assert_eq!(parsed.hash, hash);
+        assert_eq!(parsed.timestamp, Some(1_700_000_000));
+        assert_eq!(parsed.switch_proof_hash, None);
+
+        let switch = Hash::new_unique();
+        let vote = Vote::new(vec![20], hash);
+        let instruction =
+            vote_instruction::vote_switch(&vote_account, &keypair.pubkey(), vote, switch);
+        let tx = new_vote_transaction(instruction, &keypair);
+
+        let parsed = parse_vote_transaction(&tx).unwrap();
+        assert_eq!(parsed.slots, vec![20]);
+        assert_eq!(parsed.switch_proof_hash, Some(switch));
+    }
+
+    #[test]
+    fn test_parse_compact_update_vote_state() {
+        let keypair = Keypair::new();
+        let vote_account = Pubkey::new_unique();
+        let hash = Hash::new_unique();
+
+        let mut update = VoteStateUpdate::from(vec![(101, 3), (102, 2), (105, 1)]);
+        update.root = Some(100);
+        update.hash = hash;
+        let instruction =
+            vote_instruction::compact_update_vote_state(&vote_account, &keypair.pubkey(), update);
+        let tx = new_vote_transaction(instruction, &keypair);
+
+        let parsed = parse_vote_transaction(&tx).unwrap();
+        assert_eq!(parsed.vote_account, vote_account);
+        assert_eq!(parsed.slots, vec![101, 102, 105]);
+        assert_eq!(parsed.root, Some(100));
+        assert_eq!(parsed.hash, hash);
+    }
+
+    #[test]
+    fn test_parse_tower_sync() {
+        let keypair = Keypair::new();
+        let vote_account = Pubkey::new_unique();
+        let hash = Hash::new_unique();
+
+        let tower = CompactTowerSync {
+            root: 200,
+            lockout_offsets: vec![
+                LockoutOffset {
+                    offset: 1,
+                    confirmation_count: 2,
+                },
+                LockoutOffset {
+                    offset: 3,
+                    confirmation_count: 1,
+                },
+            ],
+            hash,
+            timestamp: Some(42),
+            block_id: Hash::new_unique(),
+        };
+        let mut data = TOWER_SYNC.to_le_bytes().to_vec();
+        data.extend(bincode::serialize(&tower).unwrap());
+
+        let instruction = Instruction::new_with_bytes(
+            vote::program::id(),
+            &data,
+            vec![
+                AccountMeta::new(vote_account, false),
+                AccountMeta::new_readonly(keypair.pubkey(), true),
+            ],
+        );
+        let tx = new_vote_transaction(instruction, &keypair);
+
+        let parsed = parse_vote_transaction(&tx).unwrap();
+        assert_eq!(parsed.vote_account, vote_account);
+        assert_eq!(parsed.slots, vec![201, 204]);
+        assert_eq!(parsed.root, Some(200));
+        assert_eq!(parsed.hash, hash);
+        assert_eq!(parsed.timestamp, Some(42));
+    }
+
+    #[test]
+    fn test_parse_not_a_vote() {
+        let keypair = Keypair::new();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[0; 8],
+            vec![AccountMeta::new(keypair.pubkey(), true)],
+        );
+        let tx = new_vote_transaction(instruction, &keypair);
+
+        assert_eq!(parse_vote_transaction(&tx), None);
+    }
+}
@@ -1,9 +1,12 @@
-use std::{io, net::SocketAddr, slice::SliceIndex};
+use std::{collections::HashSet, fmt, io, net::SocketAddr, slice::SliceIndex};
 
 use bincode::Options;
 use serde::Serialize;
 
-use crate::errors::Result;
+use crate::{
+    errors::Result,
+    protocol::{CrdsData, Protocol},
+};
 
 /// Maximum over-the-wire size of a Transaction
 ///   1280 is IPv6 minimum MTU
@@ -79,3 +82,347 @@ impl Payload {
         self.buf.get(..self.len)?.get(index)
     }
 }
+
+/// `Protocol` variants, in the wire discriminant order.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ProtocolKind {
+    PullRequest,
+    PullResponse,
+    PushMessage,
+    PruneMessage,
+    PingMessage,
+    PongMessage,
+}
+
+impl ProtocolKind {
+    pub const ALL: [ProtocolKind; 6] = [
+        ProtocolKind::PullRequest,
+        ProtocolKind::PullResponse,
+        ProtocolKind::PushMessage,
+        ProtocolKind::PruneMessage,
+        ProtocolKind::PingMessage,
+        ProtocolKind::PongMessage,
+    ];
+}
+
+impl From<&Protocol> for ProtocolKind {
+    fn from(protocol: &Protocol) -> Self {
+        match protocol {
+            Protocol::PullRequest(..) => ProtocolKind::PullRequest,
+            Protocol::PullResponse(..) => ProtocolKind::PullResponse,
+            Protocol::PushMessage(..) => ProtocolKind::PushMessage,
+            Protocol::PruneMessage(..) => ProtocolKind::PruneMessage,
+            Protocol::PingMessage(_) => ProtocolKind::PingMessage,
+            Protocol::PongMessage(_) => ProtocolKind::PongMessage,
+        }
+    }
+}
+
+impl fmt::Display for ProtocolKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// `CrdsData` variants, in the wire discriminant order.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CrdsKind {
+    LegacyContactInfo,
+    Vote,
+    LowestSlot,
+    SnapshotHashes,
+    AccountsHashes,
+    EpochSlots,
+    LegacyVersion,
+    Version,
+    NodeInstance,
+    DuplicateShred,
+    IncrementalSnapshotHashes,
+    ContactInfo,
+}
+
+impl CrdsKind {
+    pub const ALL: [CrdsKind; 12] = [
+        CrdsKind::LegacyContactInfo,
+        CrdsKind::Vote,
+        CrdsKind::LowestSlot,
+        CrdsKind::SnapshotHashes,
+        CrdsKind::AccountsHashes,
+        CrdsKind::EpochSlots,
+        CrdsKind::LegacyVersion,
+        CrdsKind::Version,
+        CrdsKind::NodeInstance,
+        CrdsKind::DuplicateShred,
+        CrdsKind::IncrementalSnapshotHashes,
+        CrdsKind::ContactInfo,
+    ];
+}
+
+impl From<&CrdsData> for CrdsKind {
+    fn from(data: &CrdsData) -> Self {
+        match data {
+            CrdsData::LegacyContactInfo(_) => CrdsKind::LegacyContactInfo,
+            CrdsData::Vote(..) => CrdsKind::Vote,
+            CrdsData::LowestSlot(..) => CrdsKind::LowestSlot,
+            CrdsData::SnapshotHashes(_) => CrdsKind::SnapshotHashes,
+            CrdsData::AccountsHashes(_) => CrdsKind::AccountsHashes,
+            CrdsData::EpochSlots(..) => CrdsKind::EpochSlots,
+            CrdsData::LegacyVersion(_) => CrdsKind::LegacyVersion,
+            CrdsData::Version(_) => CrdsKind::Version,
+            CrdsData::NodeInstance(_) => CrdsKind::NodeInstance,
+            CrdsData::DuplicateShred(..) => CrdsKind::DuplicateShred,
+            CrdsData::IncrementalSnapshotHashes(_) => CrdsKind::IncrementalSnapshotHashes,
+            CrdsData::ContactInfo(_) => CrdsKind::ContactInfo,
+        }
+    }
+}
+
+impl fmt::Display for CrdsKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The message kind and, for pull responses and push messages carrying a
+/// single value, the kind of the value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Header {
+    pub protocol: ProtocolKind,
+    pub crds: Option<CrdsKind>,
+}
+
+// discriminant + pubkey
+const VALUES_LEN_OFFSET: usize = 4 + 32;
+// values len + signature
+const FIRST_CRDS_DATA_OFFSET: usize = VALUES_LEN_OFFSET + 8 + 64;
+
+impl Payload {
+    /// Reads the message and the value discriminants, without decoding the
+    /// whole payload. The kinds of the values following the first one are
+    /// unknown, so the value kind is read for the single value messages only.
+    pub fn peek_header(&self) -> Option<Header> {
+        let protocol = match self.read_u32(0)? {
+            0 => ProtocolKind::PullRequest,
+            1 => ProtocolKind::PullResponse,
+            2 => ProtocolKind::PushMessage,
+            3 => ProtocolKind::PruneMessage,
+            4 => ProtocolKind::PingMessage,
+            5 => ProtocolKind::PongMessage,
+            _ => return None,
+        };
+
+        let crds = match protocol {
+            ProtocolKind::PullResponse | ProtocolKind::PushMessage => {
+                let len = self.data(VALUES_LEN_OFFSET..VALUES_LEN_OFFSET + 8)?;
+                if u64::from_le_bytes(len.try_into().ok()?) == 1 {
+                    let tag = self.read_u32(FIRST_CRDS_DATA_OFFSET)?;
+                    Some(*CrdsKind::ALL.get(usize::try_from(tag).ok()?)?)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        Some(Header { protocol, crds })
+    }
+
+    fn read_u32(&self, offset: usize) -> Option<u32> {
+        let bytes = self.data(offset..offset + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    }
+}
+
+/// The message and value kinds to process, by default all of them.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Subscriptions {
+    pub protocols: HashSet<ProtocolKind>,
+    pub crds: HashSet<CrdsKind>,
+}
+
+impl Default for Subscriptions {
+    fn default() -> Self {
+        Subscriptions {
+            protocols: ProtocolKind::ALL.into_iter().collect(),
+            crds: CrdsKind::ALL.into_iter().collect(),
+        }
+    }
+}
+
+impl Subscriptions {
+    /// Messages without a known value kind are accepted by their kind only,
+    /// their values are left to `accepts_crds`.
+    pub fn accepts(&self, header: &Header) -> bool {
+        let crds = match header.crds {
+            Some(kind) => self.crds.contains(&kind),
+            None => true,
+        };
+
+        crds && self.protocols.contains(&header.protocol)
+    }
+
+    pub fn accepts_crds(&self, data: &CrdsData) -> bool {
+        self.crds.contains(&CrdsKind::from(data))
+    }
+
+    /// Returns whether the kind is subscribed after the toggle.
+    pub fn toggle_protocol(&mut self, kind: ProtocolKind) -> bool {
+        if self.protocols.remove(&kind) {
+            false
+        } else {
+            self.protocols.insert(kind)
+        }
+    }
+
+    /// Returns whether the kind is subscribed after the toggle.
+    pub fn toggle_crds(&mut self, kind: CrdsKind) -> bool {
+        if self.crds.remove(&kind) {
+            false
+        } else {
+            self.crds.insert(kind)
+        }
+    }
+}
+
+//tests
+#[cfg(test)]
+mod tests {
+
+    use solana_sdk::{signature::Keypair, signer::Signer};
+
+    use crate::{
+        protocol::{CrdsFilter, CrdsValue, LegacyContactInfo, NodeInstance, Ping},
+        utils::{create_pull_request, create_push_message},
+    };
+
+    use super::*;
+
+    fn new_mixed_values(keypair: &Keypair, info: LegacyContactInfo) -> Vec<CrdsValue> {
+        vec![
+            CrdsValue::new_signed(
+                CrdsData::NodeInstance(NodeInstance {
+                    from: keypair.pubkey(),
+                    wallclock: 0,
+                    timestamp: 0,
+                    token: 0,
+                }),
+                keypair,
+            ),
+            CrdsValue::new_signed(CrdsData::LegacyContactInfo(Box::new(info)), keypair),
+        ]
+    }
+
+    #[test]
+    fn test_peek_header() {
+        let keypair = Keypair::new();
+        let addr: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            ..LegacyContactInfo::default()
+        };
+
+        let payload =
+            create_pull_request(info.clone(), CrdsFilter::default(), &keypair, addr).unwrap();
+        assert_eq!(
+            payload.peek_header(),
+            Some(Header {
+                protocol: ProtocolKind::PullRequest,
+                crds: None,
+            })
+        );
+
+        let values = new_mixed_values(&keypair, info);
+        let payload = create_push_message(values[..1].to_vec(), &keypair, addr).unwrap();
+        let header = payload.peek_header().unwrap();
+        assert_eq!(
+            header,
+            Header {
+                protocol: ProtocolKind::PushMessage,
+                crds: Some(CrdsKind::NodeInstance),
+            }
+        );
+
+        // the kinds of a mixed message are unknown
+        let payload = create_push_message(values, &keypair, addr).unwrap();
+        assert_eq!(
+            payload.peek_header().unwrap(),
+            Header {
+                protocol: ProtocolKind::PushMessage,
+                crds: None,
+            }
+        );
+
+        let payload = create_push_message(vec![], &keypair, addr).unwrap();
+        assert_eq!(payload.peek_header().unwrap().crds, None);
+
+        let ping = Ping::new([0; 32], &keypair).unwrap();
+        let mut payload = Payload::default();
+        payload
+            .populate_packet(Some(addr), &Protocol::PingMessage(ping))
+            .unwrap();
+        assert_eq!(
+            payload.peek_header().unwrap().protocol,
+            ProtocolKind::PingMessage
+        );
+
+        assert_eq!(Payload::default().peek_header(), None);
+    }
+
+    #[test]
+    fn test_subscriptions() {
+        let mut subscriptions = Subscriptions::default();
+        let header = Header {
+            protocol: ProtocolKind::PushMessage,
+            crds: Some(CrdsKind::Vote),
+        };
+        assert!(subscriptions.accepts(&header));
+
+        assert!(!subscriptions.toggle_crds(CrdsKind::Vote));
+        assert!(!subscriptions.accepts(&header));
+        assert!(subscriptions.accepts(&Header {
+            protocol: ProtocolKind::PushMessage,
+            crds: None,
+        }));
+
+        assert!(!subscriptions.toggle_protocol(ProtocolKind::PushMessage));
+        assert!(!subscriptions.accepts(&Header {
+            protocol: ProtocolKind::PushMessage,
+            crds: None,
+        }));
+
+        assert!(subscriptions.toggle_crds(CrdsKind::Vote));
+        assert!(subscriptions.toggle_protocol(ProtocolKind::PushMessage));
+        assert_eq!(subscriptions, Subscriptions::default());
+    }
+
+    #[test]
+    fn test_subscriptions_mixed_message() {
+        let keypair = Keypair::new();
+        let addr: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            ..LegacyContactInfo::default()
+        };
+        let values = new_mixed_values(&keypair, info);
+
+        let mut subscriptions = Subscriptions::default();
+        subscriptions.toggle_crds(CrdsKind::NodeInstance);
+
+        // an unsubscribed value alone is dropped
+        let payload = create_push_message(values[..1].to_vec(), &keypair, addr).unwrap();
+        assert!(!subscriptions.accepts(&payload.peek_header().unwrap()));
+
+        // followed by a subscribed one, the message is kept and filtered by value
+        let payload = create_push_message(values, &keypair, addr).unwrap();
+        assert!(subscriptions.accepts(&payload.peek_header().unwrap()));
+        let Protocol::PushMessage(_, values) = payload.deserialize_slice(..).unwrap() else {
+            panic!("not a push message");
+        };
+        let accepted = values
+            .iter()
+            .filter(|value| subscriptions.accepts_crds(&value.data))
+            .map(|value| CrdsKind::from(&value.data))
+            .collect::<Vec<_>>();
+        assert_eq!(accepted, vec![CrdsKind::LegacyContactInfo]);
+    }
+}
@@ -1,20 +1,123 @@
-use std::sync::mpsc::Sender;
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{mpsc::Sender, Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    thread::JoinHandle,
+};
+
+use solana_gossip_proto::{
+    crds::Crds,
+    layout::Layout,
+    node_instance::NodeInstances,
+    protocol::{NodeVersion, Slot},
+    vote_parser::ParsedVote,
+    wire::{CrdsKind, Subscriptions},
+};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Keypair};
 
 use crate::{
-    transport::CtrlCmd,
-    ui::{list_stateful_widget::StatefulList, table_stateful_widget::StatefulTable},
+    transport::{CtrlCmd, ReplayStatus},
+    ui::{
+        list_stateful_widget::StatefulList,
+        table_stateful_widget::{StatefulTable, TableView},
+    },
 };
 
+/// The entrypoints starting with it are capture files to replay.
+pub const REPLAY_PREFIX: &str = "replay:";
+
+/// How many undecodable packets are kept, the oldest are dropped first.
+pub const UNDECODED_CAPACITY: usize = 32;
+
+/// A packet the logic thread failed to decode, with the fields decoded up to
+/// the failing offset.
+#[derive(Debug)]
+pub struct UndecodedPacket {
+    pub timestamp: u64,
+    pub addr: SocketAddr,
+    pub data: Vec<u8>,
+    pub error: String,
+    pub layout: Layout,
+}
+
+/// The latest full and incremental snapshots advertised by a node.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct NodeSnapshots {
+    pub full: Option<(Slot, Hash)>,
+    pub incremental: Option<(Slot, Hash)>,
+}
+
+/// The crds table filled by the logic thread and read by the views, the
+/// logic thread notifies the labels of the changed values only.
+#[derive(Clone, Debug, Default)]
+pub struct SharedCrds(Arc<RwLock<Crds>>);
+
+impl SharedCrds {
+    pub fn read(&self) -> RwLockReadGuard<'_, Crds> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, Crds> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
 #[derive(Debug)]
 pub struct Model {
     pub debug_messages_stateful: StatefulList<String>,
     pub home_stateful_table: StatefulTable<String>,
+    /// sorting and filtering of the home table
+    pub home_table_view: TableView,
     pub home_stats_stateful_list: StatefulList<String>,
+    pub unverified_values: u32,
+    pub crds: SharedCrds,
 
     pub entrypoints_stateful: StatefulList<String>,
     pub entrypoints: Vec<String>,
     pub entrypoint: Option<String>,
     pub listern_port: u16,
+    /// node identity, loaded from the `--identity` keypair file if any
+    pub keypair: Arc<Keypair>,
+    /// cluster shred version, zero until learned from the entrypoint
+    pub shred_version: u16,
+    /// the message and value kinds processed by the threads
+    pub subscriptions: Subscriptions,
+    pub subscriptions_stateful: StatefulList<String>,
+    /// latest vote of each node
+    pub votes: HashMap<Pubkey, ParsedVote>,
+    pub votes_stateful_table: StatefulTable<String>,
+    /// recent completed slots of each node
+    pub epoch_slots: HashMap<Pubkey, BTreeSet<Slot>>,
+    pub epoch_slots_offset: usize,
+    pub snapshots: HashMap<Pubkey, NodeSnapshots>,
+    pub snapshots_stateful_table: StatefulTable<String>,
+    /// nodes agreeing on the same snapshot
+    pub snapshot_groups_stateful_list: StatefulList<String>,
+    /// restarts and duplicate instances of the nodes
+    pub instances: NodeInstances,
+    pub instance_events_stateful: StatefulList<String>,
+    /// lowest slot retained in the ledger of each node
+    pub lowest_slots: HashMap<Pubkey, Slot>,
+    pub ledger_stateful_table: StatefulTable<String>,
+    /// the nodes retaining the oldest slots first
+    pub ledger_oldest_first: bool,
+    /// client version advertised by each node
+    pub versions: HashMap<Pubkey, NodeVersion>,
+    pub versions_stateful_list: StatefulList<String>,
+    pub feature_sets_stateful_list: StatefulList<String>,
+    /// the node selected in the home table
+    pub node_details: Option<Pubkey>,
+    pub node_details_stateful: StatefulList<String>,
+    /// the capture file being recorded
+    pub capture_path: Option<PathBuf>,
+    /// the capture file replayed in place of an entrypoint
+    pub replay: Option<PathBuf>,
+    pub replay_status: Option<ReplayStatus>,
+    /// the latest packets the logic thread failed to decode
+    pub undecoded: VecDeque<UndecodedPacket>,
+    pub undecoded_stateful: StatefulList<String>,
+    pub hex_dump_scroll: u16,
 }
 
 impl Default for Model {
@@ -33,19 +136,88 @@ impl Default for Model {
         Model {
             debug_messages_stateful: StatefulList::default(),
             home_stateful_table: StatefulTable::default(),
+            // filter by IP, pubkey and version, the wallclock is shown as an age
+            // and the instance creation timestamp as an uptime
+            home_table_view: TableView::new(vec![0, 2, 3], vec![1, 4]),
             home_stats_stateful_list: StatefulList::with_items(vec![
                 "[Receiver] processed msgs #:0".to_string(),
                 "[Sender] processed msgs #:0".to_string(),
                 "[Logic] processed msgs #:0".to_string(),
+                "[Unverified] dropped values #:0".to_string(),
             ]),
+            unverified_values: 0,
+            crds: SharedCrds::default(),
             entrypoints_stateful: StatefulList::default(),
             entrypoints,
             entrypoint: None,
             listern_port: 8001,
+            keypair: Arc::new(Keypair::new()),
+            shred_version: 0,
+            subscriptions: Subscriptions {
+                crds: [
+                    CrdsKind::LegacyContactInfo,
+                    CrdsKind::LegacyVersion,
+                    CrdsKind::Version,
+                    CrdsKind::NodeInstance,
+                    CrdsKind::DuplicateShred,
+                    CrdsKind::ContactInfo,
+                ]
+                .into_iter()
+                .collect(),
+                ..Subscriptions::default()
+            },
+            subscriptions_stateful: StatefulList::default(),
+            votes: HashMap::new(),
+            votes_stateful_table: StatefulTable::default(),
+            epoch_slots: HashMap::new(),
+            epoch_slots_offset: 0,
+            snapshots: HashMap::new(),
+            snapshots_stateful_table: StatefulTable::default(),
+            snapshot_groups_stateful_list: StatefulList::default(),
+            instances: NodeInstances::new(),
+            instance_events_stateful: StatefulList::default(),
+            lowest_slots: HashMap::new(),
+            ledger_stateful_table: StatefulTable::default(),
+            ledger_oldest_first: true,
+            versions: HashMap::new(),
+            versions_stateful_list: StatefulList::default(),
+            feature_sets_stateful_list: StatefulList::default(),
+            node_details: None,
+            node_details_stateful: StatefulList::default(),
+            capture_path: None,
+            replay: None,
+            replay_status: None,
+            undecoded: VecDeque::with_capacity(UNDECODED_CAPACITY),
+            undecoded_stateful: StatefulList::default(),
+            hex_dump_scroll: 0,
         }
     }
 }
 
+impl Model {
+    /// The median of the highest slot each node claims in its votes and epoch
+    /// slots, a few nodes advertising bogus slots can not drag it along.
+    pub fn cluster_slot(&self) -> Option<Slot> {
+        let mut highest: HashMap<Pubkey, Slot> = HashMap::new();
+        let voted = self
+            .votes
+            .iter()
+            .filter_map(|(pubkey, vote)| Some((*pubkey, vote.last_voted_slot()?)));
+        let completed = self
+            .epoch_slots
+            .iter()
+            .filter_map(|(pubkey, slots)| Some((*pubkey, *slots.iter().next_back()?)));
+        for (pubkey, slot) in voted.chain(completed) {
+            let entry = highest.entry(pubkey).or_default();
+            *entry = (*entry).max(slot);
+        }
+
+        let mut slots = highest.into_values().collect::<Vec<_>>();
+        slots.sort_unstable();
+        slots.get(slots.len() / 2).copied()
+    }
+}
+
 pub struct Context {
     app_id: String,
     app_version: String,
@@ -54,6 +226,7 @@ pub struct Context {
     pub trace: bool,
 
     pub ctrl_txs: Vec<Sender<CtrlCmd>>,
+    pub recorder: Option<JoinHandle<()>>,
 }
 
 impl Context {
@@ -64,6 +237,7 @@ impl Context {
             model: Model::default(),
             trace: false,
             ctrl_txs: Vec::new(),
+            recorder: None,
         }
     }
 
@@ -89,6 +263,7 @@ mod mock_test {
                 model: Model::default(),
                 trace: false,
                 ctrl_txs: Vec::new(),
+                recorder: None,
             }
         }
     }
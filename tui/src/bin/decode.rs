@@ -0,0 +1,167 @@
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::BufReader,
+    path::PathBuf,
+    process,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::{arg, ArgGroup, Command};
+use serde_json::json;
+use solana_gossip_proto::{
+    capture::CaptureReader, json::ProtocolView, layout::Layout, protocol::Protocol, wire::Payload,
+};
+
+const APP_ID: &str = "solana_gossip_decode";
+const APP_VERSION: &str = "0.0.1+";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let matches = Command::new(APP_ID)
+        .version(APP_VERSION)
+        .about("Decodes gossip packets and shows the byte offsets of their fields")
+        .arg(arg!(--hex <HEX> "the packet as hex, whitespace is ignored").required(false))
+        .arg(arg!(--base64 <BASE64> "the packet as base64").required(false))
+        .arg(
+            arg!(--file <PATH> "a file holding the raw packet")
+                .required(false)
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .arg(
+            arg!(--capture <PATH> "a capture file, every packet is decoded")
+                .required(false)
+                .value_parser(clap::value_parser!(PathBuf)),
+        )
+        .group(
+            ArgGroup::new("input")
+                .args(["hex", "base64", "file", "capture"])
+                .required(true),
+        )
+        .arg(arg!(--json "print a JSON object per packet"))
+        .get_matches();
+
+    let as_json = matches.get_flag("json");
+
+    if let Some(path) = matches.get_one::<PathBuf>("capture") {
+        let reader = CaptureReader::new(BufReader::new(File::open(path)?))?;
+        for (index, record) in reader.enumerate() {
+            let record = record?;
+            let (protocol, layout) = decode(&record.data);
+
+            if as_json {
+                let value = json!({
+                    "index": index,
+                    "timestamp": record.timestamp,
+                    "direction": format!("{:?}", record.direction),
+                    "addr": record.addr.map(|addr| addr.to_string()),
+                    "protocol": protocol.as_ref().map(ProtocolView::from),
+                    "layout": layout,
+                });
+                println!("{value}");
+            } else {
+                let addr = record
+                    .addr
+                    .map_or_else(|| "-".to_string(), |addr| addr.to_string());
+                println!(
+                    "#{index} {} {:?} {addr} len:{}",
+                    record.timestamp,
+                    record.direction,
+                    record.data.len()
+                );
+                print!("{layout}");
+                println!();
+            }
+        }
+        return Ok(());
+    }
+
+    let bytes = if let Some(hex) = matches.get_one::<String>("hex") {
+        parse_hex(hex)?
+    } else if let Some(base64) = matches.get_one::<String>("base64") {
+        STANDARD.decode(base64.trim())?
+    } else if let Some(path) = matches.get_one::<PathBuf>("file") {
+        fs::read(path)?
+    } else {
+        vec![]
+    };
+
+    let (protocol, layout) = decode(&bytes);
+    let decoded = protocol.is_some();
+    if as_json {
+        let value = json!({
+            "protocol": protocol.as_ref().map(ProtocolView::from),
+            "layout": layout,
+        });
+        println!("{value}");
+    } else {
+        print!("{layout}");
+    }
+
+    if !decoded {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+// the protocol message, if the packet is valid, and the fields decoded from it
+fn decode(bytes: &[u8]) -> (Option<Protocol>, Layout) {
+    let mut payload = Payload {
+        len: bytes.len(),
+        ..Payload::default()
+    };
+    let protocol = match payload.buf.get_mut(..bytes.len()) {
+        Some(buf) => {
+            buf.copy_from_slice(bytes);
+            payload.deserialize_slice::<Protocol, _>(..payload.len).ok()
+        }
+        None => None,
+    };
+
+    (protocol, Layout::of::<Protocol>(bytes))
+}
+
+fn parse_hex(value: &str) -> Result<Vec<u8>, String> {
+    let digits = value
+        .trim()
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<Vec<_>>();
+    if digits.len() % 2 != 0 {
+        return Err(format!("odd number of hex digits:{}", digits.len()));
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = pair.iter().collect::<String>();
+            u8::from_str_radix(&pair, 16).map_err(|_| format!("invalid hex byte:{pair}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_gossip_proto::wire::PACKET_DATA_SIZE;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("0x0a ff\n10"), Ok(vec![10, 255, 16]));
+        assert_eq!(parse_hex(""), Ok(vec![]));
+        assert!(parse_hex("abc").is_err());
+        assert!(parse_hex("zz").is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        let (protocol, layout) = decode(&[4, 0, 0, 0, 1]);
+        assert!(protocol.is_none());
+        assert_eq!(layout.root.value.as_deref(), Some("PingMessage"));
+        assert_eq!(layout.error.map(|error| error.offset), Some(5));
+
+        let (protocol, layout) = decode(&[0; PACKET_DATA_SIZE + 1]);
+        assert!(protocol.is_none());
+        assert!(layout.error.is_some());
+    }
+}
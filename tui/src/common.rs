@@ -1,6 +1,7 @@
 use std::{
-    io,
+    fs, io,
     net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    path::{Path, PathBuf},
     sync::{mpsc, Arc},
     thread::JoinHandle,
     time::Duration,
@@ -9,19 +10,34 @@ use std::{
 use log::trace;
 
 use solana_gossip_proto::{
-    protocol::{LegacyContactInfo, Version},
-    utils::parse_addr,
+    capture::{CaptureRecord, Direction},
+    protocol::CrdsValueLabel,
+    utils::{parse_addr, since_the_epoch_millis},
     wire::Payload,
 };
 
-use crate::app::Context;
+use crate::app::{Context, UndecodedPacket};
 use crate::logic::spawn_logic;
-use crate::transport::{receiver::spawn_receiver, sender::spawn_sender, CtrlCmd, Stats};
+use crate::transport::{
+    receiver::spawn_receiver,
+    recorder::spawn_recorder,
+    replay::{load_capture, spawn_replay},
+    sender::spawn_sender,
+    CtrlCmd, ReplayCmd, ReplayStatus, Stats,
+};
 
 #[derive(Debug)]
 pub enum Data {
-    LegacyContactInfo(Box<LegacyContactInfo>),
-    Version(Version),
+    /// values inserted or updated in the shared crds table
+    Upserted(Vec<CrdsValueLabel>),
+    /// values dropped from the crds table
+    Purged(Vec<CrdsValueLabel>),
+    /// the shred version learned from the entrypoint
+    ShredVersion(u16),
+    /// the position of the capture file replay
+    Replay(ReplayStatus),
+    /// a packet that could not be decoded
+    Undecoded(Box<UndecodedPacket>),
 }
 
 #[allow(clippy::type_complexity)]
@@ -32,6 +48,10 @@ pub fn init_threads(
     mpsc::Receiver<Stats>,
     Vec<JoinHandle<()>>,
 )> {
+    if let Some(path) = ctx.model.replay.clone() {
+        return init_replay_threads(ctx, &path);
+    }
+
     let entrypoint_str = if let Some(entrypoint) = &ctx.model.entrypoint {
         entrypoint.as_str()
     } else {
@@ -42,11 +62,11 @@ pub fn init_threads(
     };
 
     let Some(entrypoint_addr) = parse_addr(entrypoint_str) else {
-          return Err(io::Error::new(
+        return Err(io::Error::new(
             io::ErrorKind::Other,
             "invalid entrypoint address",
-          ));
-        };
+        ));
+    };
 
     let gossip_local_ip_addr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
     let gossip_local_listener_addr = SocketAddr::new(gossip_local_ip_addr, ctx.model.listern_port);
@@ -81,6 +101,7 @@ pub fn init_threads(
         receiver_tx,
         ctrl_receiver_rx,
         stats_tx.clone(),
+        ctx.model.subscriptions.clone(),
     )?;
     let sender_t = spawn_sender(socket, sender_rx, ctrl_sender_rx, stats_tx.clone())?;
     let logic_t = spawn_logic(
@@ -91,7 +112,133 @@ pub fn init_threads(
         ctrl_logic_rx,
         stats_tx,
         data_tx,
+        ctx.model.crds.clone(),
+        ctx.model.keypair.clone(),
+        ctx.model.shred_version,
+        ctx.model.subscriptions.clone(),
     )?;
 
     Ok((data_rx, stats_rx, vec![receiver_t, sender_t, logic_t]))
 }
+
+/// Replays a capture file, the replay thread takes the place of the receiver
+/// one and the packets sent by the logic thread are dropped.
+#[allow(clippy::type_complexity)]
+fn init_replay_threads(
+    ctx: &mut Context,
+    path: &Path,
+) -> io::Result<(
+    mpsc::Receiver<Data>,
+    mpsc::Receiver<Stats>,
+    Vec<JoinHandle<()>>,
+)> {
+    let records = load_capture(path)?;
+    trace!(
+        "[main] replaying:{} records:{}",
+        path.display(),
+        records.len()
+    );
+
+    // the pull requests were sent to the entrypoint
+    let unspecified_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+    let entrypoint_addr = records
+        .iter()
+        .find(|record| record.direction == Direction::Sent)
+        .and_then(|record| record.addr)
+        .unwrap_or(unspecified_addr);
+    let records = records
+        .into_iter()
+        .filter(|record| record.direction == Direction::Received)
+        .collect();
+
+    // replay
+    let (ctrl_replay_tx, ctrl_replay_rx) = mpsc::channel::<CtrlCmd>();
+    ctx.ctrl_txs.push(ctrl_replay_tx);
+    let (receiver_tx, receiver_rx) = mpsc::channel::<Payload>();
+    let (sender_tx, _) = mpsc::channel::<Payload>();
+
+    // logic
+    let (ctrl_logic_tx, ctrl_logic_rx) = mpsc::channel::<CtrlCmd>();
+    ctx.ctrl_txs.push(ctrl_logic_tx);
+
+    // stats
+    let (stats_tx, stats_rx) = mpsc::channel::<Stats>();
+
+    let (data_tx, data_rx) = mpsc::channel::<Data>();
+
+    let replay_t = spawn_replay(
+        records,
+        receiver_tx,
+        ctrl_replay_rx,
+        stats_tx.clone(),
+        data_tx.clone(),
+        ctx.model.subscriptions.clone(),
+    )?;
+    let logic_t = spawn_logic(
+        unspecified_addr,
+        entrypoint_addr,
+        sender_tx,
+        receiver_rx,
+        ctrl_logic_rx,
+        stats_tx,
+        data_tx,
+        ctx.model.crds.clone(),
+        ctx.model.keypair.clone(),
+        ctx.model.shred_version,
+        ctx.model.subscriptions.clone(),
+    )?;
+
+    Ok((data_rx, stats_rx, vec![replay_t, logic_t]))
+}
+
+/// Lists the capture files of the directory, sorted by name.
+pub fn find_captures(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| matches!(path.extension(), Some(ext) if ext == "capture"))
+            .collect::<Vec<_>>(),
+        Err(_) => vec![],
+    };
+    paths.sort();
+    paths
+}
+
+/// Sends a command to the replay thread.
+pub fn control_replay(ctx: &Context, cmd: ReplayCmd) {
+    for ctrl_tx in &ctx.ctrl_txs {
+        ctrl_tx.send(CtrlCmd::Replay(cmd)).unwrap_or(());
+    }
+}
+
+/// Starts recording the received and sent packets to a new capture file in the
+/// working directory.
+pub fn start_recording(ctx: &mut Context) -> io::Result<()> {
+    let path = PathBuf::from(format!(
+        "solana-gossip-{}.capture",
+        since_the_epoch_millis()
+    ));
+    let (record_tx, record_rx) = mpsc::channel::<CaptureRecord>();
+    let recorder_t = spawn_recorder(&path, record_rx)?;
+
+    for ctrl_tx in &ctx.ctrl_txs {
+        ctrl_tx
+            .send(CtrlCmd::Record(Some(record_tx.clone())))
+            .unwrap_or(());
+    }
+    trace!("[main] recording to:{}", path.display());
+
+    ctx.recorder = Some(recorder_t);
+    ctx.model.capture_path = Some(path);
+    Ok(())
+}
+
+/// Stops recording, the recorder thread closes the file once the threads have
+/// dropped their record senders.
+pub fn stop_recording(ctx: &mut Context) {
+    for ctrl_tx in &ctx.ctrl_txs {
+        ctrl_tx.send(CtrlCmd::Record(None)).unwrap_or(());
+    }
+    ctx.model.capture_path = None;
+}
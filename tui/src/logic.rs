@@ -11,19 +11,27 @@ use std::{
 
 use log::trace;
 use solana_gossip_proto::{
-    protocol::{CrdsData, LegacyContactInfo, Ping, Protocol},
+    crds::{CrdsInsertion, CRDS_TIMEOUT_MS},
+    duplicate_shred::DuplicateShredReassembler,
+    layout::Layout,
+    protocol::{
+        CrdsData, CrdsFilter, CrdsValue, CrdsValueLabel, LegacyContactInfo, Ping, Protocol,
+        CRDS_FILTER_MAX_BYTES,
+    },
     utils::{create_pong_response, create_pull_request, since_the_epoch_millis},
-    wire::Payload,
+    wire::{Payload, Subscriptions},
 };
 use solana_sdk::{signature::Keypair, signer::Signer};
 
 use crate::{
+    app::{SharedCrds, UndecodedPacket},
     common::Data,
     transport::{CtrlCmd, Stats, StatsId},
 };
 
 pub const RECV_TIMEOUT: Duration = Duration::from_millis(30);
 
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 pub(crate) fn spawn_logic(
     gossip_local_listener_addr: SocketAddr,
     entrypoint_addr: SocketAddr,
@@ -32,15 +40,19 @@ pub(crate) fn spawn_logic(
     ctrl_rx: Receiver<CtrlCmd>,
     stats_tx: Sender<Stats>,
     data_tx: Sender<Data>,
+    crds: SharedCrds,
+    keypair_arc: Arc<Keypair>,
+    shred_version: u16,
+    mut subscriptions: Subscriptions,
 ) -> io::Result<JoinHandle<()>> {
     Builder::new().name("logic_t".to_string()).spawn(move || {
         let mut counter: u32 = 0;
+        let mut unverified: u32 = 0;
 
-        let keypair = Keypair::new();
-        let keypair_arc = Arc::new(keypair);
-        let shred_version: u16 = 0;
+        let mut duplicate_shreds = DuplicateShredReassembler::new();
+        let mut filters: Vec<CrdsFilter> = Vec::new();
 
-        let contact_info = LegacyContactInfo {
+        let mut contact_info = LegacyContactInfo {
             id: keypair_arc.pubkey(),
             gossip: gossip_local_listener_addr,
             wallclock: since_the_epoch_millis(),
@@ -59,9 +71,22 @@ pub(crate) fn spawn_logic(
                                 counter,
                             })
                             .unwrap_or(());
+                        stats_tx
+                            .send(Stats {
+                                id: StatsId::Unverified,
+                                counter: unverified,
+                            })
+                            .unwrap_or(());
 
                         trace!("counter:{counter} received CtrlCmd::Counter");
+
+                        let purged = crds.write().purge(since_the_epoch_millis(), CRDS_TIMEOUT_MS);
+                        if !purged.is_empty() {
+                            data_tx.send(Data::Purged(purged)).unwrap_or(());
+                        }
                     }
+                    CtrlCmd::Subscribe(value) => subscriptions = value,
+                    CtrlCmd::Record(_) | CtrlCmd::Replay(_) => {}
                 }
             }
             if let Ok(payload) = rx.recv_timeout(RECV_TIMEOUT) {
@@ -73,46 +98,94 @@ pub(crate) fn spawn_logic(
                     let r = payload.deserialize_slice(..);
                     match r {
                         Ok(proto) => match proto {
-                            Protocol::PingMessage(ping) => send_pong_response(
-                                &ping,
-                                from_addr,
-                                keypair_arc.as_ref(),
-                                &tx,
-                                counter,
-                            ),
+                            Protocol::PingMessage(ping) => {
+                                if ping.verify() {
+                                    send_pong_response(
+                                        &ping,
+                                        from_addr,
+                                        keypair_arc.as_ref(),
+                                        &tx,
+                                        counter,
+                                    );
+                                } else {
+                                    unverified += 1;
+                                    trace!("# len:{len} unverified PingMessage from_addr:{from_addr:?}");
+                                }
+                            }
                             Protocol::PongMessage(pong) => {
+                                if !pong.verify() {
+                                    unverified += 1;
+                                }
                                 trace!(
                                     "# len:{len} PongMessage from_addr:{from_addr:?} pong:{pong:?}",
                                 );
                             }
-                            Protocol::PullResponse(from_key, crds_values) => {
+                            Protocol::PullResponse(from_key, crds_values)
+                            | Protocol::PushMessage(from_key, crds_values) => {
                                 trace!(
-                                  "# len:{len} PullResponse from_addr:{from_addr:?} from_key:{from_key:?}"
+                                  "# len:{len} from_addr:{from_addr:?} from_key:{from_key:?} values:{}",
+                                  crds_values.len()
                                 );
-                                for value in &crds_values {
-                                    trace!("# {value:?}");
-
-                                    match &value.data {
-                                        CrdsData::LegacyContactInfo(info) => {
-                                            data_tx
-                                                .send(Data::LegacyContactInfo(info.clone()))
-                                                .unwrap_or(());
-                                        }
-                                        CrdsData::Version(version) => {
-                                            data_tx
-                                                .send(Data::Version(version.clone()))
-                                                .unwrap_or(());
-                                        }
-                                        _ => {}
+                                // the receiver filters the single value messages only
+                                let mut upserted = vec![];
+                                for value in crds_values {
+                                    if !subscriptions.accepts_crds(&value.data) {
+                                        continue;
                                     }
+                                    if value.verify() {
+                                        upserted.extend(process_crds_value(
+                                            value,
+                                            &crds,
+                                            &mut duplicate_shreds,
+                                        ));
+                                    } else {
+                                        unverified += 1;
+                                        trace!("# unverified {value}");
+                                    }
+                                }
+                                if !upserted.is_empty() {
+                                    data_tx.send(Data::Upserted(upserted)).unwrap_or(());
+                                }
+
+                                // learn the cluster shred version from the entrypoint
+                                let discovered = (contact_info.shred_version == 0
+                                    && from_addr == entrypoint_addr)
+                                    .then(|| crds.read().get_shred_version(&from_key))
+                                    .flatten();
+                                if let Some(shred_version) = discovered {
+                                    contact_info.shred_version = shred_version;
+                                    contact_info.wallclock = since_the_epoch_millis();
+                                    data_tx
+                                        .send(Data::ShredVersion(shred_version))
+                                        .unwrap_or(());
+                                }
+                            }
+                            Protocol::PruneMessage(from_key, prune_data) => {
+                                if !prune_data.verify() {
+                                    unverified += 1;
                                 }
+                                trace!(
+                                  "# len:{len} PruneMessage from_addr:{from_addr:?} from_key:{from_key:?} prunes:{:?}",
+                                  prune_data.prunes
+                                );
                             }
-                            _ => {
+                            Protocol::PullRequest(..) => {
                                 trace!("# ??? err protocol:{proto:?}");
                             }
                         },
                         Err(err) => {
                             trace!("# ??? err:{err:?}");
+                            let data = payload.data(..).unwrap_or_default().to_vec();
+                            let packet = UndecodedPacket {
+                                timestamp: since_the_epoch_millis(),
+                                addr: from_addr,
+                                layout: Layout::of::<Protocol>(&data),
+                                data,
+                                error: err.to_string(),
+                            };
+                            data_tx
+                                .send(Data::Undecoded(Box::new(packet)))
+                                .unwrap_or(());
                         }
                     }
                     trace!("#---------------------------------------- 2");
@@ -121,27 +194,69 @@ pub(crate) fn spawn_logic(
                 }
             }
 
-            send_pull_request(
-                contact_info.clone(),
-                keypair_arc.as_ref(),
-                entrypoint_addr,
-                &tx,
-                counter,
-            );
+            // rotate through the filters, rebuilt from the values received so far
+            if filters.is_empty() {
+                filters = crds.read().filters(CRDS_FILTER_MAX_BYTES);
+            }
+            if let Some(filter) = filters.pop() {
+                send_pull_request(
+                    contact_info.clone(),
+                    filter,
+                    keypair_arc.as_ref(),
+                    entrypoint_addr,
+                    &tx,
+                    counter,
+                );
+            }
         }
 
         trace!("counter:{counter} terminated");
     })
 }
 
+// the label of the value if it has been inserted in the table
+fn process_crds_value(
+    value: CrdsValue,
+    crds: &SharedCrds,
+    duplicate_shreds: &mut DuplicateShredReassembler,
+) -> Option<CrdsValueLabel> {
+    trace!("# {value:?}");
+
+    if let CrdsData::DuplicateShred(_, shred) = &value.data {
+        if let Ok(Some(proof)) = duplicate_shreds.insert(shred) {
+            trace!(
+                "# DuplicateShred from:{:?} slot:{} shred1 len:{} shred2 len:{}",
+                shred.from,
+                shred.slot,
+                proof.shred1.len(),
+                proof.shred2.len()
+            );
+        }
+    }
+
+    let label = value.label();
+
+    match crds.write().insert(value, since_the_epoch_millis()) {
+        Ok(CrdsInsertion::Inserted) => return Some(label),
+        Ok(insertion) => {
+            trace!("# {label:?} {insertion:?}");
+        }
+        Err(err) => {
+            trace!("# {label:?} err:{err:?}");
+        }
+    }
+    None
+}
+
 fn send_pull_request(
     contact_info: LegacyContactInfo,
+    filter: CrdsFilter,
     keypair: &Keypair,
     entrypoint_addr: SocketAddr,
     tx: &Sender<Payload>,
     counter: u32,
 ) {
-    let r = create_pull_request(contact_info, keypair, entrypoint_addr);
+    let r = create_pull_request(contact_info, filter, keypair, entrypoint_addr);
 
     match r {
         Ok(data) => {
@@ -7,34 +7,81 @@ mod transport;
 mod ui;
 
 use std::{
+    collections::{BTreeSet, HashMap},
     io,
-    sync::mpsc::Receiver,
+    path::Path,
+    sync::{mpsc::Receiver, Arc},
     time::{Duration, Instant},
 };
 
+use clap::{arg, Command};
 use crossterm::event::{self, Event, KeyCode};
 use log::error;
-use solana_gossip_proto::protocol::{LegacyContactInfo, LegacyVersion2};
+use solana_gossip_proto::{
+    node_instance::{InstanceEvent, NodeInstanceState},
+    protocol::{CrdsData, CrdsValueLabel, LegacyContactInfo, NodeInstance, Slot},
+    utils::load_or_create_keypair,
+    vote_parser::ParsedVote,
+};
+use solana_sdk::{hash::Hash, pubkey::Pubkey};
 use tui::{backend::Backend, Terminal};
 
 use crate::{
-    app::Context,
-    common::{init_threads, Data},
+    app::{Context, NodeSnapshots, UndecodedPacket, REPLAY_PREFIX, UNDECODED_CAPACITY},
+    common::{control_replay, find_captures, init_threads, start_recording, stop_recording, Data},
     logic::RECV_TIMEOUT,
     stm::{events, stm_main::MainStm, States},
-    transport::{CtrlCmd, Stats},
+    transport::{CtrlCmd, ReplayCmd, Stats},
 };
 
 const APP_ID: &str = "solana_gossip_tui";
 const APP_VERSION: &str = "0.0.1+";
 const STATS_INTERVAL: Duration = Duration::from_millis(1000);
+const EPOCH_SLOTS_WINDOW: Slot = 1024;
+const REPLAY_SEEK_MS: i64 = 10_000;
+const MAX_DATA_PER_FRAME: usize = 1024;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // initialize terminal state
-    let mut xterm = terminal::XTerminal::new()?;
+    let matches = Command::new(APP_ID)
+        .version(APP_VERSION)
+        .arg(
+            arg!(--identity <PATH> "the node keypair file, generated if missing")
+                .required(false)
+                .value_parser(clap::value_parser!(std::path::PathBuf)),
+        )
+        .arg(
+            arg!(--"shred-version" <VALUE> "the cluster shred version, learned if missing")
+                .required(false)
+                .value_parser(clap::value_parser!(u16)),
+        )
+        .arg(
+            arg!(--replay <PATH> "a capture file to replay instead of an entrypoint")
+                .required(false)
+                .value_parser(clap::value_parser!(std::path::PathBuf)),
+        )
+        .get_matches();
 
     // initialize app context and state machine
     let mut ctx = Context::new(String::from(APP_ID), String::from(APP_VERSION));
+    if let Some(path) = matches.get_one::<std::path::PathBuf>("identity") {
+        ctx.model.keypair = Arc::new(load_or_create_keypair(path)?);
+    }
+    if let Some(shred_version) = matches.get_one::<u16>("shred-version") {
+        ctx.model.shred_version = *shred_version;
+    }
+    // the capture files can be selected in place of the entrypoints
+    let mut captures = find_captures(Path::new("."));
+    if let Some(path) = matches.get_one::<std::path::PathBuf>("replay") {
+        captures.insert(0, path.clone());
+    }
+    for path in captures {
+        ctx.model
+            .entrypoints
+            .push(format!("{REPLAY_PREFIX}{}", path.display()));
+    }
+
+    // initialize terminal state
+    let mut xterm = terminal::XTerminal::new()?;
     let mut stm = MainStm::new("stm", true);
 
     let res = run_app(&mut xterm.terminal, &mut ctx, &mut stm);
@@ -69,10 +116,15 @@ fn run_app<B: Backend>(
             if let Event::Key(key) = event::read()? {
                 stm.on_event(events::Event::Key { key_code: key.code }, ctx);
 
-                if let KeyCode::Char('q') = key.code {
+                // the keys typed in the filter bar are not commands
+                if KeyCode::Char('q') == key.code && stm.current_st != States::HomeFilter {
                     for ctrl_tx in &ctx.ctrl_txs {
                         ctrl_tx.send(CtrlCmd::Stop).unwrap_or(());
                     }
+                    // let the capture file be flushed
+                    if let Some(recorder_t) = ctx.recorder.take() {
+                        recorder_t.join().unwrap_or(());
+                    }
                     return Ok(());
                 } else if KeyCode::Char('c') == key.code
                     && stm.current_st == States::Home
@@ -87,62 +139,71 @@ fn run_app<B: Backend>(
                     && stm.current_st == States::Home
                     && data_rx.is_some()
                 {
+                    stop_recording(ctx);
                     for ctrl_tx in &ctx.ctrl_txs {
                         ctrl_tx.send(CtrlCmd::Stop).unwrap_or(());
                     }
 
                     data_rx = None;
                     stats_rx = None;
+                    ctx.model.replay_status = None;
+                } else if KeyCode::Char('w') == key.code
+                    && stm.current_st == States::Home
+                    && data_rx.is_some()
+                    && ctx.model.replay.is_none()
+                {
+                    if ctx.model.capture_path.is_some() {
+                        stop_recording(ctx);
+                    } else if let Err(err) = start_recording(ctx) {
+                        error!("recording err:{err}");
+                    }
+                } else if stm.current_st == States::Home
+                    && data_rx.is_some()
+                    && ctx.model.replay.is_some()
+                {
+                    let cmd = match key.code {
+                        KeyCode::Char(' ') => Some(ReplayCmd::Pause),
+                        KeyCode::Char('n') => Some(ReplayCmd::Step),
+                        KeyCode::Char('+') => Some(ReplayCmd::Faster),
+                        KeyCode::Char('-') => Some(ReplayCmd::Slower),
+                        KeyCode::Char(']') => Some(ReplayCmd::Seek(REPLAY_SEEK_MS)),
+                        KeyCode::Char('[') => Some(ReplayCmd::Seek(-REPLAY_SEEK_MS)),
+                        _ => None,
+                    };
+                    if let Some(cmd) = cmd {
+                        control_replay(ctx, cmd);
+                    }
                 }
             }
         }
 
         if let Some(ref data_rx) = data_rx {
-            if let Ok(data) = data_rx.recv_timeout(RECV_TIMEOUT) {
+            // a pull response carries thousands of values, the ones changed
+            // by a whole batch are refreshed once
+            let mut labels = vec![];
+            for data in data_rx.try_iter().take(MAX_DATA_PER_FRAME) {
                 match data {
-                    Data::LegacyContactInfo(info) => {
-                        let rows = ctx
-                            .model
-                            .home_stateful_table
-                            .items
-                            .iter()
-                            .filter(|row| -> bool {
-                                let id = &row[2];
-
-                                id == &info.id.to_string()
-                            })
-                            .collect::<Vec<_>>();
-
-                        if rows.is_empty() {
-                            let row = create_row(&info);
-                            ctx.model.home_stateful_table.push_row(row);
-                        }
+                    Data::Upserted(upserted) => {
+                        observe_instances(ctx, &upserted);
+                        labels.extend(upserted);
                     }
-                    Data::Version(version) => {
-                        fn format_version(version: &LegacyVersion2) -> String {
-                            format!("{}.{}.{}", version.major, version.minor, version.patch)
-                        }
-
-                        let at_index =
-                            ctx.model
-                                .home_stateful_table
-                                .items
-                                .iter()
-                                .position(|row| -> bool {
-                                    let id = &row[2];
-
-                                    id == &version.from.to_string()
-                                });
-
-                        if let Some(index) = at_index {
-                            let row = &mut ctx.model.home_stateful_table.items[index];
+                    Data::Purged(purged) => {
+                        forget_instances(ctx, &purged);
+                        labels.extend(purged);
+                    }
+                    Data::ShredVersion(shred_version) => {
+                        ctx.model.shred_version = shred_version;
 
-                            let _ =
-                                std::mem::replace(&mut row[3], format_version(&version.version));
+                        let pubkeys = ctx.model.crds.read().pubkeys().copied().collect::<Vec<_>>();
+                        for pubkey in pubkeys {
+                            refresh_node(ctx, &pubkey);
                         }
                     }
+                    Data::Replay(status) => ctx.model.replay_status = Some(status),
+                    Data::Undecoded(packet) => push_undecoded(ctx, *packet),
                 }
             }
+            refresh_labels(ctx, &labels);
         }
 
         if let Some(ref stats_rx) = stats_rx {
@@ -157,6 +218,13 @@ fn run_app<B: Backend>(
                     transport::StatsId::Receiver => format_stats(ctx, 0, &stats),
                     transport::StatsId::Sender => format_stats(ctx, 1, &stats),
                     transport::StatsId::Logic => format_stats(ctx, 2, &stats),
+                    transport::StatsId::Unverified => {
+                        ctx.model.unverified_values = stats.counter;
+                        let _ = std::mem::replace(
+                            &mut ctx.model.home_stats_stateful_list.items[3],
+                            format!("[{:?}] dropped values #: {}", stats.id, stats.counter),
+                        );
+                    }
                 }
             }
         }
@@ -172,12 +240,382 @@ fn run_app<B: Backend>(
     }
 }
 
-fn create_row(info: &LegacyContactInfo) -> Vec<String> {
+// keeps the latest packets only, the selection follows the packet it is on
+fn push_undecoded(ctx: &mut Context, packet: UndecodedPacket) {
+    if ctx.model.undecoded.len() >= UNDECODED_CAPACITY {
+        ctx.model.undecoded.pop_front();
+
+        let state = &mut ctx.model.undecoded_stateful.state;
+        if let Some(index) = state.selected() {
+            state.select(index.checked_sub(1));
+        }
+    }
+    ctx.model.undecoded.push_back(packet);
+}
+
+// refreshes the rows depending on the changed values, once per node
+fn refresh_labels(ctx: &mut Context, labels: &[CrdsValueLabel]) {
+    let mut nodes = BTreeSet::new();
+    let mut votes = BTreeSet::new();
+    let mut epoch_slots = BTreeSet::new();
+    let mut lowest_slots = BTreeSet::new();
+    let mut snapshots = BTreeSet::new();
+    for label in labels {
+        let pubkey = label.pubkey();
+        nodes.insert(pubkey);
+        match label {
+            CrdsValueLabel::Vote(..) => {
+                votes.insert(pubkey);
+            }
+            CrdsValueLabel::EpochSlots(..) => {
+                epoch_slots.insert(pubkey);
+            }
+            CrdsValueLabel::LowestSlot(_) => {
+                lowest_slots.insert(pubkey);
+            }
+            CrdsValueLabel::SnapshotHashes(_) | CrdsValueLabel::IncrementalSnapshotHashes(_) => {
+                snapshots.insert(pubkey);
+            }
+            _ => {}
+        }
+    }
+
+    // the node rows show the lowest slots
+    for pubkey in &lowest_slots {
+        refresh_lowest_slot(ctx, pubkey);
+    }
+    for pubkey in &nodes {
+        refresh_node(ctx, pubkey);
+    }
+    for pubkey in &epoch_slots {
+        refresh_epoch_slots(ctx, pubkey);
+    }
+    if !votes.is_empty() {
+        for pubkey in &votes {
+            refresh_vote(ctx, pubkey);
+        }
+        refresh_votes_table(ctx);
+    }
+    if !snapshots.is_empty() {
+        for pubkey in &snapshots {
+            refresh_snapshots(ctx, pubkey);
+        }
+        refresh_snapshots_table(ctx);
+    }
+}
+
+// updates, adds or removes the node row according to the crds table content
+fn refresh_node(ctx: &mut Context, pubkey: &Pubkey) {
+    let crds = ctx.model.crds.read();
+    let instance = create_instance_cell(ctx.model.instances.get(pubkey));
+    let lowest_slot = ctx
+        .model
+        .lowest_slots
+        .get(pubkey)
+        .map_or_else(|| " - ".to_string(), ToString::to_string);
+    let version = crds.get_version(pubkey);
+    let row = crds.get_contact_info(pubkey).map(|info| {
+        create_row(
+            &info,
+            version.map_or_else(|| " - ".to_string(), |version| version.to_string()),
+            instance,
+            lowest_slot,
+            ctx.model.shred_version,
+        )
+    });
+
+    match version {
+        Some(version) if row.is_some() => ctx.model.versions.insert(*pubkey, version),
+        _ => ctx.model.versions.remove(pubkey),
+    };
+
+    // rows are identified by the pubkey column
+    ctx.model.home_table_view.update_row(
+        &mut ctx.model.home_stateful_table,
+        2,
+        &pubkey.to_string(),
+        row,
+    );
+}
+
+fn refresh_lowest_slot(ctx: &mut Context, pubkey: &Pubkey) {
+    let lowest = match ctx
+        .model
+        .crds
+        .read()
+        .get(&CrdsValueLabel::LowestSlot(*pubkey))
+    {
+        Some(entry) => match &entry.value.data {
+            CrdsData::LowestSlot(_, lowest_slot) => Some(lowest_slot.lowest),
+            _ => None,
+        },
+        None => None,
+    };
+
+    match lowest {
+        Some(lowest) => ctx.model.lowest_slots.insert(*pubkey, lowest),
+        None => ctx.model.lowest_slots.remove(pubkey),
+    };
+}
+
+// keeps the vote with the highest slot among the ones of the node
+fn refresh_vote(ctx: &mut Context, pubkey: &Pubkey) {
+    let latest = ctx
+        .model
+        .crds
+        .read()
+        .get_records(pubkey)
+        .filter_map(|entry| match &entry.value.data {
+            CrdsData::Vote(_, vote) => vote.parse(),
+            _ => None,
+        })
+        .max_by_key(ParsedVote::last_voted_slot);
+
+    match latest {
+        Some(vote) => ctx.model.votes.insert(*pubkey, vote),
+        None => ctx.model.votes.remove(pubkey),
+    };
+}
+
+fn refresh_votes_table(ctx: &mut Context) {
+    // the lag of every node depends on the cluster slot, a single bogus vote
+    // does not make every other node lag
+    let cluster_slot = ctx.model.cluster_slot().unwrap_or_default();
+
+    let mut votes = ctx.model.votes.iter().collect::<Vec<_>>();
+    votes.sort_by_key(|(_, vote)| vote.last_voted_slot());
+
+    ctx.model.votes_stateful_table.items = votes
+        .into_iter()
+        .map(|(pubkey, vote)| create_vote_row(pubkey, vote, cluster_slot))
+        .collect();
+}
+
+// keeps the most recent completed slots of the node, the heatmap shows a few hundreds
+fn refresh_epoch_slots(ctx: &mut Context, pubkey: &Pubkey) {
+    let mut slots = ctx
+        .model
+        .crds
+        .read()
+        .get_records(pubkey)
+        .filter_map(|entry| match &entry.value.data {
+            CrdsData::EpochSlots(_, epoch_slots) => Some(epoch_slots.completed_slots()),
+            _ => None,
+        })
+        .flatten()
+        .collect::<BTreeSet<Slot>>();
+
+    if slots.is_empty() {
+        ctx.model.epoch_slots.remove(pubkey);
+    } else {
+        if let Some(last_slot) = slots.iter().next_back() {
+            slots = slots.split_off(&last_slot.saturating_sub(EPOCH_SLOTS_WINDOW));
+        }
+        ctx.model.epoch_slots.insert(*pubkey, slots);
+    }
+}
+
+// the full snapshot is the base of the incremental ones, the legacy
+// SnapshotHashes are used by the nodes not advertising incremental snapshots
+fn refresh_snapshots(ctx: &mut Context, pubkey: &Pubkey) {
+    let crds = ctx.model.crds.read();
+    let legacy = match crds.get(&CrdsValueLabel::SnapshotHashes(*pubkey)) {
+        Some(entry) => match &entry.value.data {
+            CrdsData::SnapshotHashes(hashes) => hashes.latest(),
+            _ => None,
+        },
+        None => None,
+    };
+    let (base, incremental) = match crds.get(&CrdsValueLabel::IncrementalSnapshotHashes(*pubkey)) {
+        Some(entry) => match &entry.value.data {
+            CrdsData::IncrementalSnapshotHashes(hashes) => (Some(hashes.base), hashes.latest()),
+            _ => (None, None),
+        },
+        None => (None, None),
+    };
+
+    let snapshots = NodeSnapshots {
+        full: base.or(legacy),
+        incremental,
+    };
+    if snapshots == NodeSnapshots::default() {
+        ctx.model.snapshots.remove(pubkey);
+    } else {
+        ctx.model.snapshots.insert(*pubkey, snapshots);
+    }
+}
+
+fn refresh_snapshots_table(ctx: &mut Context) {
+    let full_groups = group_snapshots(ctx.model.snapshots.values().filter_map(|s| s.full));
+    let incremental_groups =
+        group_snapshots(ctx.model.snapshots.values().filter_map(|s| s.incremental));
+
+    let mut nodes = ctx.model.snapshots.iter().collect::<Vec<_>>();
+    nodes.sort_by_key(|(_, snapshots)| std::cmp::Reverse(snapshots.full));
+    ctx.model.snapshots_stateful_table.items = nodes
+        .into_iter()
+        .map(|(pubkey, snapshots)| {
+            let mut row = vec![format!("{pubkey:?}")];
+            row.extend(format_snapshot(snapshots.full, &full_groups));
+            row.extend(format_snapshot(snapshots.incremental, &incremental_groups));
+            row
+        })
+        .collect();
+
+    let mut groups = Vec::new();
+    for (kind, snapshot_groups) in [("full", &full_groups), ("incr", &incremental_groups)] {
+        let mut sorted = snapshot_groups.iter().collect::<Vec<_>>();
+        sorted.sort_by_key(|((slot, _), count)| std::cmp::Reverse((*slot, **count)));
+        groups.extend(sorted.into_iter().map(|((slot, hash), count)| {
+            format!("{kind} {slot} {} nodes:{count}", short_hash(hash))
+        }));
+    }
+    ctx.model.snapshot_groups_stateful_list.items = groups;
+}
+
+// number of nodes advertising each snapshot
+fn group_snapshots(snapshots: impl Iterator<Item = (Slot, Hash)>) -> HashMap<(Slot, Hash), usize> {
+    let mut groups = HashMap::new();
+    for snapshot in snapshots {
+        *groups.entry(snapshot).or_default() += 1;
+    }
+    groups
+}
+
+// a snapshot is an outlier when more nodes agree on another hash for the same slot
+fn is_outlier(snapshot: &(Slot, Hash), groups: &HashMap<(Slot, Hash), usize>) -> bool {
+    let count = groups.get(snapshot).copied().unwrap_or_default();
+    groups
+        .iter()
+        .any(|((slot, hash), other)| *slot == snapshot.0 && *hash != snapshot.1 && *other > count)
+}
+
+fn short_hash(hash: &Hash) -> String {
+    hash.to_string().chars().take(8).collect()
+}
+
+// slot, hash and nodes agreeing
+fn format_snapshot(
+    snapshot: Option<(Slot, Hash)>,
+    groups: &HashMap<(Slot, Hash), usize>,
+) -> [String; 3] {
+    match snapshot {
+        Some(snapshot) => {
+            let mut hash = short_hash(&snapshot.1);
+            if is_outlier(&snapshot, groups) {
+                hash = format!("{hash} [!]");
+            }
+            let count = groups.get(&snapshot).copied().unwrap_or_default();
+            [snapshot.0.to_string(), hash, count.to_string()]
+        }
+        None => [" - ".to_string(), " - ".to_string(), " - ".to_string()],
+    }
+}
+
+fn create_vote_row(pubkey: &Pubkey, vote: &ParsedVote, cluster_slot: Slot) -> Vec<String> {
+    fn format_slot(slot: Option<Slot>) -> String {
+        slot.map_or_else(|| " - ".to_string(), |slot| slot.to_string())
+    }
+
+    let lag = vote
+        .last_voted_slot()
+        .map(|slot| cluster_slot.saturating_sub(slot));
+    let timestamp = vote
+        .timestamp
+        .map_or_else(|| " - ".to_string(), |timestamp| timestamp.to_string());
+    vec![
+        format!("{pubkey:?}"),               // "Node Identifier",
+        format!("{:?}", vote.vote_account),  // "Vote Account",
+        format_slot(vote.last_voted_slot()), // "Last Vote",
+        format_slot(vote.root),              // "Root",
+        format_slot(lag),                    // "Lag",
+        timestamp,                           // "Timestamp",
+    ]
+}
+
+// nodes advertising a shred version different from the cluster one are flagged
+fn format_shred_version(info: &LegacyContactInfo, shred_version: u16) -> String {
+    if shred_version != 0 && info.shred_version != shred_version {
+        format!("{} [!]", info.shred_version)
+    } else {
+        format!("{}", info.shred_version)
+    }
+}
+
+// restarts and duplicate instances are logged, the first sighting is not
+fn observe_instances(ctx: &mut Context, labels: &[CrdsValueLabel]) {
+    for label in labels {
+        let instance = match ctx.model.crds.read().get(label) {
+            Some(entry) => match &entry.value.data {
+                CrdsData::NodeInstance(instance) => Some(instance.clone()),
+                _ => None,
+            },
+            None => None,
+        };
+        if let Some(instance) = instance {
+            observe_instance(ctx, &instance);
+        }
+    }
+}
+
+fn observe_instance(ctx: &mut Context, instance: &NodeInstance) {
+    let message = match ctx.model.instances.observe(instance) {
+        Some(InstanceEvent::Restarted { pubkey, token }) => {
+            format!("restarted {pubkey} token:{token:x}")
+        }
+        Some(InstanceEvent::Duplicate {
+            pubkey,
+            token,
+            other,
+        }) => format!("[!] duplicate instance {pubkey} tokens:{token:x},{other:x}"),
+        Some(InstanceEvent::Started { .. }) | None => return,
+    };
+
+    ctx.model.instance_events_stateful.push(message);
+}
+
+// the purged instances are forgotten, a node flagged as a duplicate is not
+// flagged forever
+fn forget_instances(ctx: &mut Context, labels: &[CrdsValueLabel]) {
+    for label in labels {
+        if let CrdsValueLabel::NodeInstance(pubkey) = label {
+            ctx.model.instances.remove(pubkey);
+        }
+    }
+}
+
+// the instance creation timestamp and its restarts, the uptime is computed when
+// drawn, duplicate instances are flagged
+fn create_instance_cell(state: Option<&NodeInstanceState>) -> String {
+    let Some(state) = state else {
+        return " - ".to_string();
+    };
+    if state.duplicate.is_some() {
+        return "DUP [!]".to_string();
+    }
+
+    if state.restarts > 0 {
+        format!("{} r{}", state.timestamp, state.restarts)
+    } else {
+        state.timestamp.to_string()
+    }
+}
+
+fn create_row(
+    info: &LegacyContactInfo,
+    version: String,
+    instance: String,
+    lowest_slot: String,
+    shred_version: u16,
+) -> Vec<String> {
+    let shred_ver = format_shred_version(info, shred_version);
     vec![
         format!("{}", info.gossip.ip()),         // "IP",
-        format!("{}", info.wallclock),           // "Age(ms)",
+        format!("{}", info.wallclock),           // "Age",
         format!("{:?}", info.id),                // "Node Identifier",
-        format!(" - ",),                         // "Version",
+        version,                                 // "Version",
+        instance,                                // "Instance",
+        lowest_slot,                             // "Lowest",
         format!("{}", info.gossip.port()),       // "Gossip",
         format!("{}", info.tpu_vote.port()),     // "TPUvote",
         format!("{}", info.tpu.port()),          // "TPU",
@@ -186,12 +624,139 @@ fn create_row(info: &LegacyContactInfo) -> Vec<String> {
         format!("{}", info.tvu_forwards.port()), // "TVUfwd",
         format!("{}", info.repair.port()),       // "Repair",
         format!("{}", info.serve_repair.port()), // "ServeR",
-        format!("{}", info.shred_version),       // "ShredVer",
+        shred_ver,                               // "ShredVer",
     ]
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use solana_gossip_proto::node_instance::NodeInstances;
+
+    #[test]
+    fn test_create_instance_cell() {
+        let pubkey = Pubkey::new_unique();
+        let mut instances = NodeInstances::new();
+        let mut instance = NodeInstance {
+            from: pubkey,
+            wallclock: 0,
+            timestamp: 1_000,
+            token: 1,
+        };
+        instances.observe(&instance);
+
+        assert_eq!(create_instance_cell(None), " - ");
+        assert_eq!(create_instance_cell(instances.get(&pubkey)), "1000");
+
+        instance.token = 2;
+        instance.timestamp = 2_000;
+        instances.observe(&instance);
+        assert_eq!(create_instance_cell(instances.get(&pubkey)), "2000 r1");
+
+        instance.token = 1;
+        instance.timestamp = 1_000;
+        instances.observe(&instance);
+        assert_eq!(create_instance_cell(instances.get(&pubkey)), "DUP [!]");
+    }
+
+    #[test]
+    fn test_forget_instances() {
+        let mut ctx = Context::new_for_testing();
+        let pubkey = Pubkey::new_unique();
+        let mut instance = NodeInstance {
+            from: pubkey,
+            wallclock: 0,
+            timestamp: 1_000,
+            token: 1,
+        };
+        ctx.model.instances.observe(&instance);
+        instance.token = 2;
+        ctx.model.instances.observe(&instance);
+        assert_eq!(ctx.model.instances.duplicates().count(), 1);
+
+        forget_instances(&mut ctx, &[CrdsValueLabel::LegacyVersion(pubkey)]);
+        assert!(ctx.model.instances.get(&pubkey).is_some());
+
+        forget_instances(&mut ctx, &[CrdsValueLabel::NodeInstance(pubkey)]);
+        assert_eq!(ctx.model.instances.get(&pubkey), None);
+        assert_eq!(ctx.model.instances.duplicates().count(), 0);
+    }
+
+    #[test]
+    fn test_format_shred_version() {
+        let info = LegacyContactInfo {
+            shred_version: 503,
+            ..LegacyContactInfo::default()
+        };
+
+        assert_eq!(format_shred_version(&info, 0), "503");
+        assert_eq!(format_shred_version(&info, 503), "503");
+        assert_eq!(format_shred_version(&info, 50093), "503 [!]");
+    }
+
+    #[test]
+    fn test_format_snapshot() {
+        let (hash1, hash2) = (Hash::new_unique(), Hash::new_unique());
+        let groups =
+            group_snapshots([(100, hash1), (100, hash1), (100, hash2), (90, hash2)].into_iter());
+
+        let row = format_snapshot(Some((100, hash1)), &groups);
+        assert_eq!(
+            row,
+            ["100".to_string(), short_hash(&hash1), "2".to_string()]
+        );
+
+        let row = format_snapshot(Some((100, hash2)), &groups);
+        assert_eq!(row[1], format!("{} [!]", short_hash(&hash2)));
+        assert_eq!(row[2], "1");
+
+        // the only one for its slot
+        let row = format_snapshot(Some((90, hash2)), &groups);
+        assert!(!row[1].ends_with("[!]"));
+
+        assert_eq!(format_snapshot(None, &groups)[0], " - ");
+    }
+
+    #[test]
+    fn test_create_vote_row() {
+        let vote = ParsedVote {
+            vote_account: Pubkey::default(),
+            slots: vec![98, 99, 100],
+            root: None,
+            hash: Hash::default(),
+            timestamp: Some(1_700_000_000),
+            switch_proof_hash: None,
+        };
+
+        let row = create_vote_row(&Pubkey::default(), &vote, 110);
+        assert_eq!(row[2], "100");
+        assert_eq!(row[3], " - ");
+        assert_eq!(row[4], "10");
+        assert_eq!(row[5], "1700000000");
+    }
+
+    #[test]
+    fn test_refresh_votes_table() {
+        let mut ctx = Context::new_for_testing();
+        for last_slot in [100, 110, u64::MAX] {
+            let vote = ParsedVote {
+                vote_account: Pubkey::new_unique(),
+                slots: vec![last_slot],
+                root: None,
+                hash: Hash::default(),
+                timestamp: None,
+                switch_proof_hash: None,
+            };
+            ctx.model.votes.insert(Pubkey::new_unique(), vote);
+        }
+
+        refresh_votes_table(&mut ctx);
+        let rows = &ctx.model.votes_stateful_table.items;
+        assert_eq!(rows[0][4], "10");
+        assert_eq!(rows[1][4], "0");
+        assert_eq!(rows[2][4], "0");
+    }
+
     #[test]
     fn test_format_version() {
         let mut items = vec![
@@ -13,6 +13,15 @@ pub enum States {
     EntrypointSelection,
     Home,
     Help,
+    Subscriptions,
+    Votes,
+    EpochSlots,
+    Snapshots,
+    Ledger,
+    Versions,
+    NodeDetails,
+    HomeFilter,
+    HexDump,
 }
 
 trait State {
@@ -39,7 +48,16 @@ trait State {
 pub(crate) mod events;
 mod state_debug;
 mod state_entrypoint_selection;
+mod state_epoch_slots;
 mod state_help;
+mod state_hex_dump;
 mod state_home;
+mod state_home_filter;
+mod state_ledger;
+mod state_node_details;
+mod state_snapshots;
+mod state_subscriptions;
 mod state_unknown;
+mod state_versions;
+mod state_votes;
 pub(crate) mod stm_main;
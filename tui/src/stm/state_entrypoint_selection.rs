@@ -1,8 +1,9 @@
 use super::{events::Event, State, States};
-use crate::app::Context;
+use crate::app::{Context, REPLAY_PREFIX};
 use crate::ui::core::{centered_rect, draw_box};
 use crate::ui::list_stateful_widget::draw_stateful_list;
 use crossterm::event::KeyCode;
+use std::path::PathBuf;
 use tui::{backend::Backend, Frame};
 
 pub struct EntrypointSelectionState {
@@ -55,8 +56,10 @@ impl State for EntrypointSelectionState {
                 if let Some(index) = ctx.model.entrypoints_stateful.state.selected() {
                     let entrypoint = ctx.model.entrypoints_stateful.items.get(index);
 
-                    if entrypoint.is_some() {
-                        ctx.model.entrypoint = entrypoint.cloned();
+                    if let Some(entrypoint) = entrypoint {
+                        ctx.model.replay =
+                            entrypoint.strip_prefix(REPLAY_PREFIX).map(PathBuf::from);
+                        ctx.model.entrypoint = Some(entrypoint.clone());
                         return Some(States::Home);
                     }
                 }
@@ -92,7 +95,7 @@ impl State for EntrypointSelectionState {
         UP     -> previous
         DOWN   -> next
         LEFT   -> deselect
-        ENTER  -> select
+        ENTER  -> select, replay:<path> replays a capture
 
         D      -> show Debug
         q      -> Quit program
@@ -145,6 +148,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_replay_selection() {
+        let mut ctx = Context::new_for_testing();
+        ctx.model.entrypoints_stateful.items = vec![
+            "127.0.0.1:8001".to_string(),
+            "replay:solana-gossip-1.capture".to_string(),
+        ];
+
+        let mut state = EntrypointSelectionState::default();
+        let enter = Event::Key {
+            key_code: KeyCode::Enter,
+        };
+
+        ctx.model.entrypoints_stateful.state.select(Some(1));
+        assert_eq!(state.on_event(enter.clone(), &mut ctx), Some(States::Home));
+        assert_eq!(
+            ctx.model.replay,
+            Some(PathBuf::from("solana-gossip-1.capture"))
+        );
+
+        ctx.model.entrypoints_stateful.state.select(Some(0));
+        assert_eq!(state.on_event(enter, &mut ctx), Some(States::Home));
+        assert_eq!(ctx.model.replay, None);
+        assert_eq!(ctx.model.entrypoint, Some("127.0.0.1:8001".to_string()));
+    }
+
     #[test]
     fn test_ui() {
         let backend = TestBackend::new(7, 4);
@@ -173,7 +202,7 @@ mod tests {
     #[test]
     fn test_state_help() -> Result<(), String> {
         let state = EntrypointSelectionState::default();
-        assert_eq!(state.help_text().len(), 196);
+        assert_eq!(state.help_text().len(), 229);
 
         Ok(())
     }
@@ -0,0 +1,162 @@
+use super::{events::Event, State, States};
+use crate::ui::core::{centered_rect, draw_box};
+use crate::{app::Context, ui::heatmap_widget::draw_heatmap};
+use crossterm::event::KeyCode;
+use solana_gossip_proto::wire::CrdsKind;
+use tui::{backend::Backend, Frame};
+
+pub struct EpochSlotsState;
+
+impl State for EpochSlotsState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                if ctx.model.epoch_slots_offset + 1 < ctx.model.epoch_slots.len() {
+                    ctx.model.epoch_slots_offset += 1;
+                }
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                ctx.model.epoch_slots_offset = ctx.model.epoch_slots_offset.saturating_sub(1);
+                None
+            }
+            _ => {
+                ctx.debug(format!("[EpochSlotsS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        let size = f.size();
+        draw_box(f, size, " Epoch Slots ");
+
+        // a node claiming a far future slot does not move the window
+        let last_slot = ctx.model.cluster_slot().unwrap_or_default();
+
+        let mut title = format!("Completed slots up to {last_slot}");
+        if !ctx.model.subscriptions.crds.contains(&CrdsKind::EpochSlots) {
+            title = format!("{title} [!] EpochSlots values not subscribed");
+        }
+
+        let mut rows = ctx
+            .model
+            .epoch_slots
+            .iter()
+            .map(|(pubkey, slots)| (pubkey.to_string(), slots))
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let bbox = centered_rect(95, 90, size);
+        let rows = rows
+            .into_iter()
+            .skip(ctx.model.epoch_slots_offset)
+            .collect::<Vec<_>>();
+        draw_heatmap(f, bbox, &title, &rows, last_slot);
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> scroll up
+        DOWN   -> scroll down
+
+        █      -> slot completed by the node
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use solana_sdk::pubkey::Pubkey;
+    use std::collections::BTreeSet;
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_epoch_slots_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        ctx.model
+            .epoch_slots
+            .insert(Pubkey::new_unique(), BTreeSet::from([1, 2]));
+        ctx.model
+            .epoch_slots
+            .insert(Pubkey::new_unique(), BTreeSet::from([2, 3]));
+
+        let mut state = EpochSlotsState;
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event.clone(), &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.epoch_slots_offset, 1);
+
+        // the last row stays visible
+        state.on_event(event, &mut ctx);
+        assert_eq!(ctx.model.epoch_slots_offset, 1);
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.epoch_slots_offset, 0);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        // the heatmap window ends at the cluster slot, not at a bogus one
+        ctx.model
+            .epoch_slots
+            .insert(Pubkey::new_unique(), BTreeSet::from([u64::MAX - 1]));
+        assert_eq!(ctx.model.cluster_slot(), Some(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = EpochSlotsState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let expected = Buffer::with_lines(vec![
+      "┌Comp┐╮",
+      "│    ││",
+      "└────┘│",
+      "╰─────╯"
+      ]);
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = EpochSlotsState;
+        assert_eq!(state.help_text().len(), 139);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,244 @@
+use super::{events::Event, State, States};
+use crate::app::{Context, UndecodedPacket};
+use crate::ui::core::{draw_box, format_timestamp, layout_rows_30_70};
+use crate::ui::{hex_dump_widget::draw_hex_dump, list_stateful_widget::draw_stateful_list};
+use crossterm::event::KeyCode;
+use tui::{backend::Backend, Frame};
+
+const SCROLL_LINES: u16 = 8;
+
+pub struct HexDumpState;
+
+impl HexDumpState {
+    fn format_packet(packet: &UndecodedPacket) -> String {
+        let time = format_timestamp(packet.timestamp);
+        format!(
+            "{} {:<21} len:{:<4} {}",
+            time.get(11..23).unwrap_or_default(),
+            packet.addr,
+            packet.data.len(),
+            packet.error
+        )
+    }
+
+    fn refresh_list(ctx: &mut Context) {
+        ctx.model.undecoded_stateful.items = ctx
+            .model
+            .undecoded
+            .iter()
+            .map(Self::format_packet)
+            .collect();
+    }
+
+    // the selected packet, the latest one if none is selected
+    fn packet(ctx: &Context) -> Option<&UndecodedPacket> {
+        match ctx.model.undecoded_stateful.state.selected() {
+            Some(index) => ctx.model.undecoded.get(index),
+            None => ctx.model.undecoded.back(),
+        }
+    }
+}
+
+impl State for HexDumpState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                Self::refresh_list(ctx);
+                ctx.model.undecoded_stateful.next();
+                ctx.model.hex_dump_scroll = 0;
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                Self::refresh_list(ctx);
+                ctx.model.undecoded_stateful.previous();
+                ctx.model.hex_dump_scroll = 0;
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Left,
+            } => {
+                ctx.model.undecoded_stateful.unselect();
+                ctx.model.hex_dump_scroll = 0;
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::PageDown,
+            } => {
+                ctx.model.hex_dump_scroll = ctx.model.hex_dump_scroll.saturating_add(SCROLL_LINES);
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::PageUp,
+            } => {
+                ctx.model.hex_dump_scroll = ctx.model.hex_dump_scroll.saturating_sub(SCROLL_LINES);
+                None
+            }
+            _ => {
+                ctx.debug(format!("[HexDumpS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        let size = f.size();
+        draw_box(f, size, " Undecoded Packets ");
+
+        Self::refresh_list(ctx);
+
+        let bboxs = layout_rows_30_70(size);
+        let title = format!(" latest {} undecoded packets ", ctx.model.undecoded.len());
+        draw_stateful_list(
+            f,
+            bboxs[0],
+            &title,
+            &mut ctx.model.undecoded_stateful,
+            false,
+        );
+
+        if let Some(packet) = Self::packet(ctx) {
+            let title = format!(" {} len:{} ", packet.addr, packet.data.len());
+            draw_hex_dump(
+                f,
+                bboxs[1],
+                &title,
+                &packet.data,
+                &packet.layout,
+                ctx.model.hex_dump_scroll,
+            );
+        }
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> previous
+        DOWN   -> next
+        LEFT   -> deselect, show the latest
+        PGUP   -> scroll the dump up
+        PGDOWN -> scroll the dump down
+
+        magenta -> enum tags and lengths
+        cyan    -> pubkeys, hashes and signatures
+        yellow  -> wallclocks
+        red     -> from the failing offset on
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use solana_gossip_proto::{layout::Layout, protocol::Protocol};
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn new_packet(data: &[u8]) -> UndecodedPacket {
+        UndecodedPacket {
+            timestamp: 1_700_000_000_000,
+            addr: "10.0.0.1:8001".parse().unwrap(),
+            data: data.to_vec(),
+            error: "io error".to_string(),
+            layout: Layout::of::<Protocol>(data),
+        }
+    }
+
+    #[test]
+    fn test_hex_dump_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        ctx.model.undecoded.push_back(new_packet(&[4, 0, 0, 0, 1]));
+        ctx.model.undecoded.push_back(new_packet(&[9, 0, 0, 0]));
+
+        // the latest packet is shown until one is selected
+        assert_eq!(HexDumpState::packet(&ctx).unwrap().data, vec![9, 0, 0, 0]);
+
+        let mut state = HexDumpState;
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(
+            ctx.model.undecoded_stateful.items[0],
+            "22:13:20.000 10.0.0.1:8001         len:5    io error"
+        );
+        assert_eq!(HexDumpState::packet(&ctx).unwrap().data.len(), 5);
+
+        let event = Event::Key {
+            key_code: KeyCode::PageDown,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.hex_dump_scroll, SCROLL_LINES);
+
+        let event = Event::Key {
+            key_code: KeyCode::PageUp,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.hex_dump_scroll, 0);
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Left,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.undecoded_stateful.state.selected(), None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = HexDumpState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let expected = Buffer::with_lines(vec![
+      "┌ late┐",
+      "│     │",
+      "│     │",
+      "╰─────╯"
+      ]);
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = HexDumpState;
+        assert_eq!(state.help_text().len(), 371);
+
+        Ok(())
+    }
+}
@@ -1,10 +1,86 @@
 use super::{events::Event, State, States};
-use crate::ui::core::draw_box;
+use crate::ui::core::{draw_box, format_duration, format_timestamp};
 use crate::ui::list_stateful_widget::draw_stateful_list;
-use crate::ui::table_stateful_widget::draw_stateful_table;
-use crate::{app::Context, ui::core::layout_columns_70_30};
+use crate::ui::table_stateful_widget::draw_stateful_table_with;
+use crate::{
+    app::Context,
+    ui::core::{layout_columns_70_30, layout_rows_30_70},
+};
 use crossterm::event::KeyCode;
-use tui::{backend::Backend, layout::Constraint, Frame};
+use solana_gossip_proto::{crds::CRDS_TIMEOUT_MS, utils::since_the_epoch_millis};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+use std::str::FromStr;
+use tui::{
+    backend::Backend,
+    layout::Constraint,
+    style::{Color, Style},
+    widgets::{Cell, Row},
+    Frame,
+};
+
+const HEADERS: [&str; 15] = [
+    "IP",
+    "Age",
+    "Node Identifier",
+    "Version",
+    "Instance",
+    "Lowest",
+    "Gossip",
+    "TPUvote",
+    "TPU",
+    "TPUfwd",
+    "TVU",
+    "TVUfwd",
+    "Repair",
+    "ServeR",
+    "ShredVer",
+];
+
+// nodes not refreshing their contact info are shown as stale, and dropped
+// after the crds timeout
+const STALE_MS: u64 = 30_000;
+
+/// The instance creation timestamp turned into an uptime, the restarts and the
+/// duplicate flag kept as they are.
+fn format_instance(cell: &str, now: u64) -> String {
+    let (timestamp, restarts) = cell.split_once(' ').unwrap_or((cell, ""));
+    match timestamp.parse::<u64>() {
+        Ok(timestamp) if restarts.is_empty() => {
+            format_duration(now.saturating_sub(timestamp) / 1000)
+        }
+        Ok(timestamp) => format!(
+            "{} {restarts}",
+            format_duration(now.saturating_sub(timestamp) / 1000)
+        ),
+        Err(_) => cell.to_string(),
+    }
+}
+
+/// The home row as shown, the wallclock turned into an age, the instance
+/// into an uptime and the stale nodes colored.
+fn format_row(row: &[String], now: u64) -> Row<'static> {
+    let age = row
+        .get(1)
+        .and_then(|wallclock| wallclock.parse::<u64>().ok())
+        .map(|wallclock| now.saturating_sub(wallclock));
+
+    let cells = row
+        .iter()
+        .enumerate()
+        .map(|(column, cell)| match (column, age) {
+            (1, Some(age)) => Cell::from(format_duration(age / 1000)),
+            (4, _) => Cell::from(format_instance(cell, now)),
+            _ => Cell::from(cell.clone()),
+        })
+        .collect::<Vec<_>>();
+
+    let style = match age {
+        Some(age) if age > CRDS_TIMEOUT_MS => Style::default().fg(Color::Red),
+        Some(age) if age > STALE_MS => Style::default().fg(Color::Yellow),
+        _ => Style::default(),
+    };
+    Row::new(cells).style(style)
+}
 
 pub struct HomeState {
     pub on_enter_first: bool,
@@ -18,7 +94,46 @@ impl Default for HomeState {
     }
 }
 
-impl HomeState {}
+impl HomeState {
+    // the entrypoint, the warnings and the recording or replay status
+    fn title(ctx: &Context) -> String {
+        let mut title = format!("Nodes Info [{:?}]", ctx.model.entrypoint);
+        if ctx.model.shred_version != 0 {
+            title = format!("{title} shred version:{}", ctx.model.shred_version);
+        }
+        let duplicates = ctx.model.instances.duplicates().count();
+        if duplicates > 0 {
+            title = format!("{title} [!] duplicate instances:{duplicates}");
+        }
+        if ctx.model.unverified_values > 0 {
+            title = format!(
+                "{title} [!] unverified values:{}",
+                ctx.model.unverified_values
+            );
+        }
+        if !ctx.model.undecoded.is_empty() {
+            title = format!(
+                "{title} [!] undecoded packets:{}",
+                ctx.model.undecoded.len()
+            );
+        }
+        if let Some(path) = &ctx.model.capture_path {
+            title = format!("{title} [REC {}]", path.display());
+        }
+        if let Some(status) = &ctx.model.replay_status {
+            title = format!(
+                "{title} [REPLAY {}/{} x{} {}{}]",
+                status.index,
+                status.total,
+                status.speed,
+                format_timestamp(status.timestamp),
+                if status.paused { " paused" } else { "" }
+            );
+        }
+
+        title
+    }
+}
 
 impl State for HomeState {
     fn on_enter_once(&mut self, _ctx: &mut Context) {
@@ -46,6 +161,35 @@ impl State for HomeState {
 
                 None
             }
+            Event::Key {
+                key_code: KeyCode::Char('o'),
+            } => {
+                ctx.model.home_table_view.next_sort_column(HEADERS.len());
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Char('O'),
+            } => {
+                ctx.model.home_table_view.toggle_sort_order();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Enter,
+            } => {
+                let table = &ctx.model.home_stateful_table;
+                let pubkey = table
+                    .state
+                    .selected()
+                    .and_then(|index| table.items.get(index))
+                    .and_then(|row| Pubkey::from_str(&row[2]).ok());
+
+                if pubkey.is_some() {
+                    ctx.model.node_details = pubkey;
+                    return Some(States::NodeDetails);
+                }
+
+                None
+            }
             _ => {
                 ctx.debug(format!("[HomeS] on_event {event:?} not match"));
                 None
@@ -55,57 +199,83 @@ impl State for HomeState {
 
     fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
         let size = f.size();
-        draw_box(f, size, " Solana Home ");
+        let header = format!(" Solana Home [{}] ", ctx.model.keypair.pubkey());
+        draw_box(f, size, &header);
 
         let bboxs = layout_columns_70_30(size);
 
-        let title = format!("Nodes Info [{:?}]", ctx.model.entrypoint);
+        let mut title = Self::title(ctx);
+
+        let view = &mut ctx.model.home_table_view;
+        view.apply(&mut ctx.model.home_stateful_table);
+        if !view.filter.is_empty() {
+            title = format!(
+                "{title} filter:/{} [{}/{}]",
+                view.filter,
+                ctx.model.home_stateful_table.items.len(),
+                view.len(&ctx.model.home_stateful_table)
+            );
+        }
+        let headers = HEADERS
+            .iter()
+            .enumerate()
+            .map(|(column, header)| match view.sort_column {
+                Some(sort_column) if sort_column == column && view.descending => {
+                    format!("{header}▼")
+                }
+                Some(sort_column) if sort_column == column => format!("{header}▲"),
+                _ => (*header).to_string(),
+            })
+            .collect::<Vec<_>>();
+        let headers = headers.iter().map(String::as_str).collect::<Vec<_>>();
 
         let withs = [
-            Constraint::Percentage(14),
-            Constraint::Percentage(6),
-            Constraint::Percentage(16),
-            Constraint::Percentage(6),
-            Constraint::Percentage(6),
-            Constraint::Percentage(6),
-            Constraint::Percentage(6),
-            Constraint::Percentage(6),
-            Constraint::Percentage(6),
-            Constraint::Percentage(6),
-            Constraint::Percentage(6),
+            Constraint::Percentage(10),
             Constraint::Percentage(6),
+            Constraint::Percentage(10),
+            Constraint::Percentage(9),
+            Constraint::Percentage(8),
+            Constraint::Percentage(7),
+            Constraint::Percentage(5),
+            Constraint::Percentage(5),
+            Constraint::Percentage(5),
+            Constraint::Percentage(5),
+            Constraint::Percentage(5),
+            Constraint::Percentage(5),
+            Constraint::Percentage(5),
+            Constraint::Percentage(5),
             Constraint::Percentage(6),
         ];
-        draw_stateful_table(
+        // the ages of the replayed values are relative to the capture time
+        let now = ctx
+            .model
+            .replay_status
+            .map_or_else(since_the_epoch_millis, |status| status.timestamp);
+        draw_stateful_table_with(
             f,
             bboxs[0],
             &title,
-            &[
-                "IP",
-                "Age(ms)",
-                "Node Identifier",
-                "Version",
-                "Gossip",
-                "TPUvote",
-                "TPU",
-                "TPUfwd",
-                "TVU",
-                "TVUfwd",
-                "Repair",
-                "ServeR",
-                "ShredVer",
-            ],
+            &headers,
             &withs,
             &mut ctx.model.home_stateful_table,
+            |row| format_row(row, now),
         );
 
+        let bboxs = layout_rows_30_70(bboxs[1]);
         draw_stateful_list(
             f,
-            bboxs[1],
+            bboxs[0],
             " stats ",
             &mut ctx.model.home_stats_stateful_list,
             false,
         );
+        draw_stateful_list(
+            f,
+            bboxs[1],
+            " instance events ",
+            &mut ctx.model.instance_events_stateful,
+            false,
+        );
     }
 
     fn help_text(&self) -> &str {
@@ -113,10 +283,26 @@ impl State for HomeState {
         ESC    -> back
         c      -> connect to entrypoint
         d      -> disconnect from entrypoint
+        w      -> start/stop recording a capture
+        SPACE  -> pause/resume the replay
+        n      -> replay the next packet while paused
+        [ ]    -> seek the replay 10s backward/forward
+        - +    -> slow down/speed up the replay
+        s      -> select the processed messages
+        v      -> show the validators votes
+        e      -> show the epoch slots heatmap
+        h      -> show the snapshot hashes
+        l      -> show the ledger retention
+        r      -> show the release versions
+        x      -> show the undecoded packets
 
         UP     -> previous
         DOWN   -> next
         LEFT   -> deselect
+        ENTER  -> show the node details
+        o      -> sort by the next column
+        O      -> reverse the sort order
+        /      -> filter by IP, pubkey or version
 
         D      -> show Debug
         q      -> Quit program
@@ -160,9 +346,125 @@ mod tests {
         let to_state = state.on_event(event, &mut ctx);
         assert_eq!(to_state, None);
 
+        let event = Event::Key {
+            key_code: KeyCode::Char('o'),
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.home_table_view.sort_column, Some(0));
+
+        let event = Event::Key {
+            key_code: KeyCode::Char('O'),
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert!(ctx.model.home_table_view.descending);
+
+        // nothing selected
+        let event = Event::Key {
+            key_code: KeyCode::Enter,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let pubkey = Pubkey::new_unique();
+        ctx.model.home_stateful_table.push_row(vec![
+            "127.0.0.1".to_string(),
+            "0".to_string(),
+            pubkey.to_string(),
+        ]);
+        ctx.model.home_stateful_table.next_row();
+
+        let event = Event::Key {
+            key_code: KeyCode::Enter,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::NodeDetails));
+        assert_eq!(ctx.model.node_details, Some(pubkey));
+
         Ok(())
     }
 
+    #[test]
+    fn test_format_row() {
+        let row = |wallclock: &str| vec!["127.0.0.1".to_string(), wallclock.to_string()];
+        let now = 100_000;
+
+        assert_eq!(
+            format_row(&row("88000"), now),
+            Row::new(vec![Cell::from("127.0.0.1"), Cell::from("12s")])
+        );
+        assert_eq!(
+            format_row(&row("40000"), now),
+            Row::new(vec![Cell::from("127.0.0.1"), Cell::from("1m")])
+                .style(Style::default().fg(Color::Yellow))
+        );
+        assert_eq!(
+            format_row(&row("1000"), now),
+            Row::new(vec![Cell::from("127.0.0.1"), Cell::from("1m")])
+                .style(Style::default().fg(Color::Red))
+        );
+        // a wallclock ahead of the local clock
+        assert_eq!(
+            format_row(&row("200000"), now),
+            Row::new(vec![Cell::from("127.0.0.1"), Cell::from("0s")])
+        );
+    }
+
+    #[test]
+    fn test_format_instance() {
+        assert_eq!(format_instance(" - ", 0), " - ");
+        assert_eq!(format_instance("DUP [!]", 0), "DUP [!]");
+        assert_eq!(format_instance("1000", 6_000), "5s");
+        assert_eq!(format_instance("1000", 7_201_000), "2h0m");
+        assert_eq!(format_instance("2000 r1", 182_000), "3m r1");
+
+        // the uptime keeps going between the refreshes of the row
+        let row = vec![
+            "127.0.0.1".to_string(),
+            "88000".to_string(),
+            Pubkey::new_unique().to_string(),
+            "1.14.17".to_string(),
+            "1000".to_string(),
+        ];
+        let cells = |age: &str, uptime: &str| {
+            let mut cells = row
+                .iter()
+                .map(|cell| Cell::from(cell.clone()))
+                .collect::<Vec<_>>();
+            cells[1] = Cell::from(age.to_string());
+            cells[4] = Cell::from(uptime.to_string());
+            Row::new(cells)
+        };
+        assert_eq!(format_row(&row, 100_000), cells("12s", "1m"));
+        assert_eq!(
+            format_row(&row, 3_688_000),
+            cells("1h0m", "1h1m").style(Style::default().fg(Color::Red))
+        );
+    }
+
+    #[test]
+    fn test_sort_instance() {
+        let mut ctx = Context::new_for_testing();
+        for timestamp in ["1000", "5000"] {
+            ctx.model.home_stateful_table.push_row(vec![
+                "127.0.0.1".to_string(),
+                "0".to_string(),
+                Pubkey::new_unique().to_string(),
+                "1.14.17".to_string(),
+                timestamp.to_string(),
+            ]);
+        }
+
+        let view = &mut ctx.model.home_table_view;
+        while view.sort_column != Some(4) {
+            view.next_sort_column(HEADERS.len());
+        }
+        view.apply(&mut ctx.model.home_stateful_table);
+        // the most recent instance, with the shortest uptime, first
+        assert_eq!(ctx.model.home_stateful_table.items[0][4], "5000");
+    }
+
     #[test]
     fn test_ui() {
         let backend = TestBackend::new(7, 4);
@@ -191,7 +493,7 @@ mod tests {
     #[test]
     fn test_state_help() -> Result<(), String> {
         let state = HomeState::default();
-        assert_eq!(state.help_text().len(), 256);
+        assert_eq!(state.help_text().len(), 992);
 
         Ok(())
     }
@@ -0,0 +1,146 @@
+use super::{events::Event, State, States};
+use crate::app::Context;
+use crate::ui::core::{clear_box, draw_box, draw_paragraph};
+use crossterm::event::KeyCode;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// Edits the filter of the home table, every key is part of the filter text.
+pub struct HomeFilterState;
+
+impl State for HomeFilterState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        let filter = &mut ctx.model.home_table_view.filter;
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => {
+                filter.clear();
+                Some(States::Home)
+            }
+            Event::Key {
+                key_code: KeyCode::Enter,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Backspace,
+            } => {
+                filter.pop();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Char(c),
+            } => {
+                filter.push(c);
+                None
+            }
+            _ => {
+                ctx.debug(format!("[HomeFilterS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        // overlay the filter bar to the bottom of the home view
+        let size = f.size();
+        let height = size.height.min(3);
+        let bbox = Rect::new(size.x, size.bottom() - height, size.width, height);
+        clear_box(f, bbox);
+        draw_box(f, bbox, " filter by IP, pubkey or version ");
+
+        let inner = Rect::new(
+            bbox.x + 1,
+            bbox.y + 1,
+            bbox.width.saturating_sub(2),
+            bbox.height.saturating_sub(2),
+        );
+        let text = format!("/{}_", ctx.model.home_table_view.filter);
+        draw_paragraph(f, inner, &text);
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC       -> clear the filter
+        ENTER     -> keep the filter
+        BACKSPACE -> delete the last char
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_home_filter_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+
+        let mut state = HomeFilterState;
+
+        for c in ['1', '0', 'x'] {
+            let event = Event::Key {
+                key_code: KeyCode::Char(c),
+            };
+            let to_state = state.on_event(event, &mut ctx);
+            assert_eq!(to_state, None);
+        }
+
+        let event = Event::Key {
+            key_code: KeyCode::Backspace,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.home_table_view.filter, "10");
+
+        let event = Event::Key {
+            key_code: KeyCode::Enter,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+        assert_eq!(ctx.model.home_table_view.filter, "10");
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+        assert!(ctx.model.home_table_view.filter.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+        ctx.model.home_table_view.filter = "ab".to_string();
+
+        let state = HomeFilterState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let expected = Buffer::with_lines(vec![
+      "       ",
+      " filt─╮",
+      "│/ab_ │",
+      "╰─────╯"
+      ]);
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = HomeFilterState;
+        assert_eq!(state.help_text().len(), 126);
+
+        Ok(())
+    }
+}
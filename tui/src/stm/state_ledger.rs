@@ -0,0 +1,272 @@
+use super::{events::Event, State, States};
+use crate::ui::core::{centered_rect, draw_box, format_duration};
+use crate::{app::Context, ui::table_stateful_widget::draw_stateful_table};
+use crossterm::event::KeyCode;
+use solana_gossip_proto::wire::CrdsKind;
+use tui::{backend::Backend, layout::Constraint, Frame};
+
+// approximate slot duration
+const SLOT_MS: u64 = 400;
+
+pub struct LedgerState;
+
+impl LedgerState {
+    fn refresh_rows(ctx: &mut Context) {
+        let cluster_slot = ctx.model.cluster_slot();
+
+        let mut nodes = ctx
+            .model
+            .lowest_slots
+            .iter()
+            .map(|(pubkey, lowest)| (pubkey, *lowest))
+            .collect::<Vec<_>>();
+        if ctx.model.ledger_oldest_first {
+            nodes.sort_by_key(|(pubkey, lowest)| (*lowest, **pubkey));
+        } else {
+            nodes.sort_by_key(|(pubkey, lowest)| (std::cmp::Reverse(*lowest), **pubkey));
+        }
+
+        let crds = ctx.model.crds.read();
+        ctx.model.ledger_stateful_table.items = nodes
+            .into_iter()
+            .map(|(pubkey, lowest)| {
+                let info = crds.get_contact_info(pubkey);
+                let retained = cluster_slot.map(|slot| slot.saturating_sub(lowest));
+                vec![
+                    format!("{pubkey:?}"),
+                    info.as_ref()
+                        .map_or_else(|| " - ".to_string(), |info| info.gossip.ip().to_string()),
+                    info.as_ref()
+                        .map_or_else(|| " - ".to_string(), |info| info.rpc.port().to_string()),
+                    lowest.to_string(),
+                    retained.map_or_else(|| " - ".to_string(), |slots| slots.to_string()),
+                    retained.map_or_else(
+                        || " - ".to_string(),
+                        |slots| format_duration(slots.saturating_mul(SLOT_MS) / 1000),
+                    ),
+                ]
+            })
+            .collect();
+    }
+}
+
+impl State for LedgerState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                ctx.model.ledger_stateful_table.next_row();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                ctx.model.ledger_stateful_table.previous_row();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Left,
+            } => {
+                ctx.model.ledger_stateful_table.unselect();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Char('o'),
+            } => {
+                ctx.model.ledger_oldest_first = !ctx.model.ledger_oldest_first;
+                None
+            }
+            _ => {
+                ctx.debug(format!("[LedgerS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        let size = f.size();
+        draw_box(f, size, " Ledger ");
+
+        Self::refresh_rows(ctx);
+
+        let mut title = format!("Nodes [{}]", ctx.model.lowest_slots.len());
+        if let Some(slot) = ctx.model.cluster_slot() {
+            title = format!("{title} cluster slot:{slot}");
+        }
+        if !ctx.model.subscriptions.crds.contains(&CrdsKind::LowestSlot) {
+            title = format!("{title} [!] LowestSlot values not subscribed");
+        }
+
+        let withs = [
+            Constraint::Percentage(34),
+            Constraint::Percentage(16),
+            Constraint::Percentage(8),
+            Constraint::Percentage(14),
+            Constraint::Percentage(14),
+            Constraint::Percentage(14),
+        ];
+        let bbox = centered_rect(95, 90, size);
+        draw_stateful_table(
+            f,
+            bbox,
+            &title,
+            &[
+                "Node Identifier",
+                "IP",
+                "RPC",
+                "Lowest Slot",
+                "Retained",
+                "Time",
+            ],
+            &withs,
+            &mut ctx.model.ledger_stateful_table,
+        );
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> previous
+        DOWN   -> next
+        LEFT   -> deselect
+        o      -> toggle the order by lowest slot
+
+        Retained -> slots from the lowest one to the cluster slot
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use solana_sdk::pubkey::Pubkey;
+    use std::collections::BTreeSet;
+    use tui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Style},
+        Terminal,
+    };
+
+    #[test]
+    fn test_ledger_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        let (old, recent) = (Pubkey::new_unique(), Pubkey::new_unique());
+        ctx.model.lowest_slots.insert(old, 1_000);
+        ctx.model.lowest_slots.insert(recent, 9_000);
+        ctx.model
+            .epoch_slots
+            .insert(recent, BTreeSet::from([9_500, 10_000]));
+
+        LedgerState::refresh_rows(&mut ctx);
+        let rows = &ctx.model.ledger_stateful_table.items;
+        assert_eq!(rows[0][0], format!("{old:?}"));
+        assert_eq!(rows[0][4], "9000");
+        assert_eq!(rows[0][5], "1h0m");
+        assert_eq!(rows[1][4], "1000");
+
+        // a single node claiming a bogus slot does not move the cluster slot
+        let (bogus, honest) = (Pubkey::new_unique(), Pubkey::new_unique());
+        ctx.model
+            .epoch_slots
+            .insert(bogus, BTreeSet::from([u64::MAX]));
+        ctx.model
+            .epoch_slots
+            .insert(honest, BTreeSet::from([9_900]));
+        assert_eq!(ctx.model.cluster_slot(), Some(10_000));
+        LedgerState::refresh_rows(&mut ctx);
+        assert_eq!(ctx.model.ledger_stateful_table.items[0][4], "9000");
+
+        // nor overflows the retained duration when most nodes agree on it
+        let other = Pubkey::new_unique();
+        ctx.model
+            .epoch_slots
+            .insert(other, BTreeSet::from([u64::MAX]));
+        LedgerState::refresh_rows(&mut ctx);
+        assert_eq!(
+            ctx.model.ledger_stateful_table.items[0][4],
+            (u64::MAX - 1_000).to_string()
+        );
+
+        let mut state = LedgerState;
+
+        let event = Event::Key {
+            key_code: KeyCode::Char('o'),
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        LedgerState::refresh_rows(&mut ctx);
+        assert_eq!(
+            ctx.model.ledger_stateful_table.items[0][0],
+            format!("{recent:?}")
+        );
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Left,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = LedgerState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let mut expected = Buffer::with_lines(vec![
+      "┌Node┐╮",
+      "│N   ││",
+      "└────┘│",
+      "╰─────╯"
+      ]);
+        expected.set_style(Rect::new(1, 1, 4, 1), Style::default().bg(Color::LightBlue));
+        expected.set_style(Rect::new(1, 1, 1, 1), Style::default().fg(Color::White));
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = LedgerState;
+        assert_eq!(state.help_text().len(), 227);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+use std::net::SocketAddr;
+
+use super::{events::Event, State, States};
+use crate::ui::core::{centered_rect, clear_box, draw_box, format_duration, format_timestamp};
+use crate::{app::Context, ui::list_stateful_widget::draw_stateful_list};
+use crossterm::event::KeyCode;
+use solana_gossip_proto::{
+    crds::Crds,
+    protocol::{socket_tag_name, CrdsData, CrdsValueLabel},
+    utils::since_the_epoch_millis,
+};
+use solana_sdk::pubkey::Pubkey;
+use tui::{backend::Backend, Frame};
+
+pub struct NodeDetailsState;
+
+impl NodeDetailsState {
+    // every socket of the node, from the new ContactInfo if any
+    fn sockets(crds: &Crds, pubkey: &Pubkey) -> Vec<(&'static str, SocketAddr)> {
+        if let Some(CrdsData::ContactInfo(info)) = crds
+            .get(&CrdsValueLabel::ContactInfo(*pubkey))
+            .map(|entry| &entry.value.data)
+        {
+            return info
+                .resolve_sockets()
+                .into_iter()
+                .map(|(key, addr)| (socket_tag_name(key), addr))
+                .collect();
+        }
+
+        crds.get_contact_info(pubkey)
+            .map(|info| {
+                vec![
+                    ("gossip", info.gossip),
+                    ("tvu", info.tvu),
+                    ("tvu_forwards", info.tvu_forwards),
+                    ("repair", info.repair),
+                    ("tpu", info.tpu),
+                    ("tpu_forwards", info.tpu_forwards),
+                    ("tpu_vote", info.tpu_vote),
+                    ("rpc", info.rpc),
+                    ("rpc_pubsub", info.rpc_pubsub),
+                    ("serve_repair", info.serve_repair),
+                ]
+            })
+            .unwrap_or_default()
+    }
+
+    fn format_time(millis: u64, now: u64) -> String {
+        let age = format_duration(now.saturating_sub(millis) / 1000);
+        format!("{} ({age} ago)", format_timestamp(millis))
+    }
+
+    fn refresh_lines(ctx: &mut Context, now: u64) {
+        let Some(pubkey) = ctx.model.node_details else {
+            ctx.model.node_details_stateful.clear();
+            return;
+        };
+        let crds = ctx.model.crds.read();
+        let mut lines = vec![format!("identifier     {pubkey}")];
+
+        if let Some(info) = crds.get_contact_info(&pubkey) {
+            lines.push(format!("shred version  {}", info.shred_version));
+            lines.push(format!(
+                "wallclock      {}",
+                Self::format_time(info.wallclock, now)
+            ));
+        }
+        if let Some(version) = crds.get_version(&pubkey) {
+            lines.push(format!(
+                "version        {version} feature set {}",
+                version
+                    .feature_set_hex()
+                    .unwrap_or_else(|| " - ".to_string())
+            ));
+        }
+        if let Some(state) = ctx.model.instances.get(&pubkey) {
+            let mut instance = format!(
+                "instance       {:016x} started {} restarts {}",
+                state.token,
+                Self::format_time(state.timestamp, now),
+                state.restarts
+            );
+            if let Some(other) = state.duplicate {
+                instance = format!("{instance} [!] duplicate {other:016x}");
+            }
+            lines.push(instance);
+        }
+        if let Some(slot) = ctx.model.lowest_slots.get(&pubkey) {
+            lines.push(format!("lowest slot    {slot}"));
+        }
+
+        lines.push(String::new());
+        lines.push("sockets".to_string());
+        for (name, addr) in Self::sockets(&crds, &pubkey) {
+            lines.push(format!("  {name:<18} {addr}"));
+        }
+
+        let mut values = crds
+            .get_records(&pubkey)
+            .map(|entry| {
+                let index = match entry.value.label() {
+                    CrdsValueLabel::Vote(index, _) | CrdsValueLabel::EpochSlots(index, _) => {
+                        Some(u16::from(index))
+                    }
+                    CrdsValueLabel::DuplicateShred(index, _) => Some(index),
+                    _ => None,
+                };
+                let name = match index {
+                    Some(index) => format!("{}[{index}]", entry.value),
+                    None => entry.value.to_string(),
+                };
+                (name, entry.value.wallclock(), entry.local_timestamp)
+            })
+            .collect::<Vec<_>>();
+        values.sort();
+
+        lines.push(String::new());
+        lines.push(format!("values [{}]", values.len()));
+        for (name, wallclock, received) in values {
+            lines.push(format!(
+                "  {name:<28} wallclock {} received {}",
+                Self::format_time(wallclock, now),
+                format_timestamp(received)
+            ));
+        }
+
+        ctx.model.node_details_stateful.items = lines;
+    }
+}
+
+impl State for NodeDetailsState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            // reached from the home only, back to it also after the help view
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                ctx.model.node_details_stateful.next();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                ctx.model.node_details_stateful.previous();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Left,
+            } => {
+                ctx.model.node_details_stateful.unselect();
+                None
+            }
+            _ => {
+                ctx.debug(format!("[NodeDetailsS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        // the ages of the replayed values are relative to the capture time
+        let now = ctx
+            .model
+            .replay_status
+            .map_or_else(since_the_epoch_millis, |status| status.timestamp);
+        Self::refresh_lines(ctx, now);
+
+        // overlay the popup to the home view
+        let bbox = centered_rect(80, 80, f.size());
+        clear_box(f, bbox);
+        draw_box(f, bbox, " Node Details ");
+
+        let purged = match ctx.model.node_details {
+            Some(pubkey) => ctx.model.crds.read().get_records(&pubkey).next().is_none(),
+            None => true,
+        };
+        let title = if purged {
+            " [!] no values from the node "
+        } else {
+            ""
+        };
+        draw_stateful_list(
+            f,
+            centered_rect(95, 90, bbox),
+            title,
+            &mut ctx.model.node_details_stateful,
+            false,
+        );
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> previous
+        DOWN   -> next
+        LEFT   -> deselect
+
+        wallclock -> time set by the node and its age
+        received  -> local time the value has been inserted
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::ReplayStatus;
+    use crossterm::event::KeyCode;
+    use solana_gossip_proto::protocol::{CrdsValue, LegacyContactInfo};
+    use solana_sdk::{signature::Keypair, signer::Signer};
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_node_details_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        let keypair = Keypair::new();
+        let info = LegacyContactInfo {
+            id: keypair.pubkey(),
+            wallclock: 1_700_000_000_000,
+            rpc: "10.0.0.1:8899".parse().unwrap(),
+            ..LegacyContactInfo::default()
+        };
+        let value = CrdsValue::new_signed(CrdsData::LegacyContactInfo(Box::new(info)), &keypair);
+        ctx.model
+            .crds
+            .write()
+            .insert(value, 1_700_000_001_000)
+            .unwrap();
+        ctx.model.node_details = Some(keypair.pubkey());
+
+        NodeDetailsState::refresh_lines(&mut ctx, 1_700_000_005_000);
+        let lines = &ctx.model.node_details_stateful.items;
+        assert_eq!(lines[0], format!("identifier     {}", keypair.pubkey()));
+        assert_eq!(
+            lines[2],
+            "wallclock      2023-11-14 22:13:20.000 UTC (5s ago)"
+        );
+        assert!(lines.contains(&"  rpc                10.0.0.1:8899".to_string()));
+        assert_eq!(
+            lines.last().unwrap(),
+            "  LegacyContactInfo            wallclock 2023-11-14 22:13:20.000 UTC (5s ago) \
+             received 2023-11-14 22:13:21.000 UTC"
+        );
+
+        ctx.model.replay_status = Some(ReplayStatus {
+            timestamp: 1_700_000_010_000,
+            ..ReplayStatus::default()
+        });
+        let mut terminal = Terminal::new(TestBackend::new(7, 4)).unwrap();
+        terminal.draw(|f| NodeDetailsState.ui(f, &mut ctx)).unwrap();
+        assert_eq!(
+            ctx.model.node_details_stateful.items[2],
+            "wallclock      2023-11-14 22:13:20.000 UTC (10s ago)"
+        );
+
+        let mut state = NodeDetailsState;
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Left,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = NodeDetailsState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let expected = Buffer::with_lines(vec![
+      "┌ [┐╮  ",
+      "└──┘│  ",
+      "╰───╯  ",
+      "       "
+      ]);
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = NodeDetailsState;
+        assert_eq!(state.help_text().len(), 225);
+
+        Ok(())
+    }
+}
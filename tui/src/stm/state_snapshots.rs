@@ -0,0 +1,181 @@
+use super::{events::Event, State, States};
+use crate::ui::core::{draw_box, layout_columns_70_30};
+use crate::ui::list_stateful_widget::draw_stateful_list;
+use crate::{app::Context, ui::table_stateful_widget::draw_stateful_table};
+use crossterm::event::KeyCode;
+use solana_gossip_proto::wire::CrdsKind;
+use tui::{backend::Backend, layout::Constraint, Frame};
+
+pub struct SnapshotsState;
+
+impl State for SnapshotsState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                ctx.model.snapshots_stateful_table.next_row();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                ctx.model.snapshots_stateful_table.previous_row();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Left,
+            } => {
+                ctx.model.snapshots_stateful_table.unselect();
+                None
+            }
+            _ => {
+                ctx.debug(format!("[SnapshotsS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        let size = f.size();
+        draw_box(f, size, " Snapshots ");
+
+        let bboxs = layout_columns_70_30(size);
+
+        let mut title = format!("Nodes [{}]", ctx.model.snapshots.len());
+        let subscriptions = &ctx.model.subscriptions.crds;
+        if !subscriptions.contains(&CrdsKind::SnapshotHashes)
+            || !subscriptions.contains(&CrdsKind::IncrementalSnapshotHashes)
+        {
+            title = format!("{title} [!] snapshot hashes values not subscribed");
+        }
+
+        let withs = [
+            Constraint::Percentage(28),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+            Constraint::Percentage(8),
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+            Constraint::Percentage(8),
+        ];
+        draw_stateful_table(
+            f,
+            bboxs[0],
+            &title,
+            &[
+                "Node Identifier",
+                "Full Slot",
+                "Full Hash",
+                "Nodes",
+                "Incr Slot",
+                "Incr Hash",
+                "Nodes",
+            ],
+            &withs,
+            &mut ctx.model.snapshots_stateful_table,
+        );
+
+        draw_stateful_list(
+            f,
+            bboxs[1],
+            " agreement ",
+            &mut ctx.model.snapshot_groups_stateful_list,
+            false,
+        );
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> previous
+        DOWN   -> next
+        LEFT   -> deselect
+
+        Nodes  -> nodes advertising the same snapshot
+        [!]    -> more nodes agree on another hash
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_snapshots_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        ctx.model
+            .snapshots_stateful_table
+            .push_row(vec!["node".to_string()]);
+
+        let mut state = SnapshotsState;
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.snapshots_stateful_table.state.selected(), Some(0));
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Left,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.snapshots_stateful_table.state.selected(), None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = SnapshotsState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let expected = Buffer::with_lines(vec![
+      " Snap─╮",
+      "│ ┌┐┌ │",
+      "│ └┘└ │",
+      "╰─────╯"
+      ]);
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = SnapshotsState;
+        assert_eq!(state.help_text().len(), 216);
+
+        Ok(())
+    }
+}
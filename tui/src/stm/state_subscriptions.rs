@@ -0,0 +1,227 @@
+use super::{events::Event, State, States};
+use crate::ui::core::{centered_rect, draw_box};
+use crate::{app::Context, transport::CtrlCmd, ui::list_stateful_widget::draw_stateful_list};
+use crossterm::event::KeyCode;
+use solana_gossip_proto::wire::{CrdsKind, ProtocolKind, Subscriptions};
+use tui::{backend::Backend, Frame};
+
+pub struct SubscriptionsState {
+    pub on_enter_first: bool,
+}
+
+impl Default for SubscriptionsState {
+    fn default() -> Self {
+        Self {
+            on_enter_first: true,
+        }
+    }
+}
+
+impl SubscriptionsState {
+    // the message kinds first, then the crds value kinds
+    fn toggle(ctx: &mut Context, index: usize) {
+        let subscriptions = &mut ctx.model.subscriptions;
+        if let Some(kind) = ProtocolKind::ALL.get(index) {
+            subscriptions.toggle_protocol(*kind);
+        } else if let Some(kind) = CrdsKind::ALL.get(index - ProtocolKind::ALL.len()) {
+            subscriptions.toggle_crds(*kind);
+        } else {
+            return;
+        }
+
+        ctx.model.subscriptions_stateful.items = format_items(&ctx.model.subscriptions);
+
+        for ctrl_tx in &ctx.ctrl_txs {
+            ctrl_tx
+                .send(CtrlCmd::Subscribe(ctx.model.subscriptions.clone()))
+                .unwrap_or(());
+        }
+    }
+}
+
+fn format_items(subscriptions: &Subscriptions) -> Vec<String> {
+    fn check(subscribed: bool) -> &'static str {
+        if subscribed {
+            "[x]"
+        } else {
+            "[ ]"
+        }
+    }
+
+    let protocols = ProtocolKind::ALL
+        .iter()
+        .map(|kind| format!("{} {kind}", check(subscriptions.protocols.contains(kind))));
+    let crds = CrdsKind::ALL
+        .iter()
+        .map(|kind| format!("{} CRDS {kind}", check(subscriptions.crds.contains(kind))));
+
+    protocols.chain(crds).collect()
+}
+
+impl State for SubscriptionsState {
+    fn on_enter_once(&mut self, ctx: &mut Context) {
+        self.on_enter_first = false;
+
+        ctx.model.subscriptions_stateful.items = format_items(&ctx.model.subscriptions);
+    }
+
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                ctx.model.subscriptions_stateful.next();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                ctx.model.subscriptions_stateful.previous();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Left,
+            } => {
+                ctx.model.subscriptions_stateful.unselect();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Enter | KeyCode::Char(' '),
+            } => {
+                if let Some(index) = ctx.model.subscriptions_stateful.state.selected() {
+                    Self::toggle(ctx, index);
+                }
+                None
+            }
+            _ => {
+                ctx.debug(format!("[SubscriptionsS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        let size = f.size();
+        draw_box(f, size, " Subscriptions ");
+
+        let bbox = centered_rect(75, 70, size);
+        draw_stateful_list(
+            f,
+            bbox,
+            " processed messages ",
+            &mut ctx.model.subscriptions_stateful,
+            false,
+        );
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> previous
+        DOWN   -> next
+        LEFT   -> deselect
+        ENTER  -> toggle
+
+        q      -> Quit program
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use std::sync::mpsc;
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_subscriptions_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        let (ctrl_tx, ctrl_rx) = mpsc::channel();
+        ctx.ctrl_txs.push(ctrl_tx);
+
+        let mut state = SubscriptionsState::default();
+        state.on_enter_once(&mut ctx);
+        assert_eq!(
+            ctx.model.subscriptions_stateful.items.len(),
+            ProtocolKind::ALL.len() + CrdsKind::ALL.len()
+        );
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        // PullRequest
+        let event = Event::Key {
+            key_code: KeyCode::Enter,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert!(!ctx
+            .model
+            .subscriptions
+            .protocols
+            .contains(&ProtocolKind::PullRequest));
+        assert_eq!(ctx.model.subscriptions_stateful.items[0], "[ ] PullRequest");
+        assert!(matches!(ctrl_rx.try_recv(), Ok(CtrlCmd::Subscribe(_))));
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Left,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = SubscriptionsState::default();
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let expected = Buffer::with_lines(vec![
+      "┌ pr┐─╮",
+      "└───┘ │",
+      "│     │",
+      "╰─────╯"
+      ]);
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = SubscriptionsState::default();
+        assert_eq!(state.help_text().len(), 167);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use super::{events::Event, State, States};
+use crate::ui::core::{draw_box, layout_columns_50_50};
+use crate::{app::Context, ui::list_stateful_widget::draw_stateful_list};
+use crossterm::event::KeyCode;
+use solana_gossip_proto::{protocol::NodeVersion, wire::CrdsKind};
+use tui::{backend::Backend, Frame};
+
+pub struct VersionsState;
+
+impl VersionsState {
+    // nodes per key, the most common first
+    fn distribution(keys: impl Iterator<Item = String>) -> Vec<String> {
+        let mut counts = HashMap::<String, usize>::new();
+        for key in keys {
+            *counts.entry(key).or_default() += 1;
+        }
+        let total = counts.values().sum::<usize>().max(1);
+
+        let mut counts = counts.into_iter().collect::<Vec<_>>();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        counts
+            .into_iter()
+            .map(|(key, count)| format!("{count:>5} {:>3}%  {key}", count * 100 / total))
+            .collect()
+    }
+
+    fn refresh_lists(ctx: &mut Context) {
+        let versions = &ctx.model.versions;
+
+        ctx.model.versions_stateful_list.items =
+            Self::distribution(versions.values().map(NodeVersion::to_string));
+        ctx.model.feature_sets_stateful_list.items =
+            Self::distribution(versions.values().map(|version| {
+                version
+                    .feature_set_hex()
+                    .unwrap_or_else(|| " - ".to_string())
+            }));
+    }
+}
+
+impl State for VersionsState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                ctx.model.versions_stateful_list.next();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                ctx.model.versions_stateful_list.previous();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Left,
+            } => {
+                ctx.model.versions_stateful_list.unselect();
+                None
+            }
+            _ => {
+                ctx.debug(format!("[VersionsS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        let size = f.size();
+        draw_box(f, size, " Versions ");
+
+        Self::refresh_lists(ctx);
+
+        let bboxs = layout_columns_50_50(size);
+
+        let mut title = format!(" versions of {} nodes ", ctx.model.versions.len());
+        let subscriptions = &ctx.model.subscriptions.crds;
+        if !subscriptions.contains(&CrdsKind::LegacyVersion)
+            && !subscriptions.contains(&CrdsKind::Version)
+            && !subscriptions.contains(&CrdsKind::ContactInfo)
+        {
+            title = format!("{title}[!] version values not subscribed ");
+        }
+        draw_stateful_list(
+            f,
+            bboxs[0],
+            &title,
+            &mut ctx.model.versions_stateful_list,
+            false,
+        );
+
+        draw_stateful_list(
+            f,
+            bboxs[1],
+            " feature sets ",
+            &mut ctx.model.feature_sets_stateful_list,
+            false,
+        );
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> previous
+        DOWN   -> next
+        LEFT   -> deselect
+
+        version      -> major.minor.patch and commit
+        feature sets -> first 4 bytes of the feature set id
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use solana_sdk::pubkey::Pubkey;
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn new_version(patch: u16, feature_set: Option<u32>) -> NodeVersion {
+        NodeVersion {
+            major: 1,
+            minor: 18,
+            patch,
+            commit: Some(0xabcd),
+            feature_set,
+        }
+    }
+
+    #[test]
+    fn test_versions_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        for (patch, feature_set) in [(2, Some(1)), (3, Some(2)), (3, Some(2)), (1, None)] {
+            ctx.model
+                .versions
+                .insert(Pubkey::new_unique(), new_version(patch, feature_set));
+        }
+
+        VersionsState::refresh_lists(&mut ctx);
+        assert_eq!(
+            ctx.model.versions_stateful_list.items,
+            vec![
+                "    2  50%  1.18.3 0000abcd",
+                "    1  25%  1.18.1 0000abcd",
+                "    1  25%  1.18.2 0000abcd",
+            ]
+        );
+        assert_eq!(
+            ctx.model.feature_sets_stateful_list.items,
+            vec![
+                "    2  50%  00000002",
+                "    1  25%   - ",
+                "    1  25%  00000001"
+            ]
+        );
+
+        let mut state = VersionsState;
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Left,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = VersionsState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let expected = Buffer::with_lines(vec![
+      " Vers─╮",
+      "│ ┌┌┐ │",
+      "│ └└┘ │",
+      "╰─────╯"
+      ]);
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = VersionsState;
+        assert_eq!(state.help_text().len(), 224);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use super::{events::Event, State, States};
+use crate::ui::core::{centered_rect, draw_box};
+use crate::{app::Context, ui::table_stateful_widget::draw_stateful_table};
+use crossterm::event::KeyCode;
+use solana_gossip_proto::wire::CrdsKind;
+use tui::{backend::Backend, layout::Constraint, Frame};
+
+pub struct VotesState;
+
+impl State for VotesState {
+    fn on_event(&mut self, event: Event, ctx: &mut Context) -> Option<States> {
+        match event {
+            Event::Key {
+                key_code: KeyCode::Esc,
+            } => Some(States::Home),
+            Event::Key {
+                key_code: KeyCode::Down,
+            } => {
+                ctx.model.votes_stateful_table.next_row();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Up,
+            } => {
+                ctx.model.votes_stateful_table.previous_row();
+                None
+            }
+            Event::Key {
+                key_code: KeyCode::Left,
+            } => {
+                ctx.model.votes_stateful_table.unselect();
+                None
+            }
+            _ => {
+                ctx.debug(format!("[VotesS] on_event {event:?} not match"));
+                None
+            }
+        }
+    }
+
+    fn ui<B: Backend>(&self, f: &mut Frame<B>, ctx: &mut Context) {
+        let size = f.size();
+        draw_box(f, size, " Votes ");
+
+        let mut title = format!("Voters [{}]", ctx.model.votes.len());
+        if !ctx.model.subscriptions.crds.contains(&CrdsKind::Vote) {
+            title = format!("{title} [!] Vote values not subscribed");
+        }
+
+        let withs = [
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(8),
+            Constraint::Percentage(12),
+        ];
+        let bbox = centered_rect(95, 90, size);
+        draw_stateful_table(
+            f,
+            bbox,
+            &title,
+            &[
+                "Node Identifier",
+                "Vote Account",
+                "Last Vote",
+                "Root",
+                "Lag",
+                "Timestamp",
+            ],
+            &withs,
+            &mut ctx.model.votes_stateful_table,
+        );
+    }
+
+    fn help_text(&self) -> &str {
+        r##"
+        ESC    -> back
+
+        UP     -> previous
+        DOWN   -> next
+        LEFT   -> deselect
+
+        Lag    -> slots behind the cluster slot
+        "##
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use tui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Style},
+        Terminal,
+    };
+
+    #[test]
+    fn test_votes_state() -> Result<(), String> {
+        let mut ctx = Context::new_for_testing();
+        ctx.model
+            .votes_stateful_table
+            .push_row(vec!["node".to_string()]);
+
+        let mut state = VotesState;
+
+        let event = Event::Key {
+            key_code: KeyCode::Down,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.votes_stateful_table.state.selected(), Some(0));
+
+        let event = Event::Key {
+            key_code: KeyCode::Up,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Left,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, None);
+        assert_eq!(ctx.model.votes_stateful_table.state.selected(), None);
+
+        let event = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        let to_state = state.on_event(event, &mut ctx);
+        assert_eq!(to_state, Some(States::Home));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ui() {
+        let backend = TestBackend::new(7, 4);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut ctx = Context::new_for_testing();
+
+        let state = VotesState;
+
+        terminal
+            .draw(|f| {
+                state.ui(f, &mut ctx);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let mut expected = Buffer::with_lines(vec![
+      "┌Vote┐╮",
+      "│N V ││",
+      "└────┘│",
+      "╰─────╯"
+      ]);
+        expected.set_style(Rect::new(1, 1, 4, 1), Style::default().bg(Color::LightBlue));
+        expected.set_style(Rect::new(1, 1, 1, 1), Style::default().fg(Color::White));
+        expected.set_style(Rect::new(3, 1, 1, 1), Style::default().fg(Color::White));
+
+        terminal.backend().assert_buffer(&expected);
+    }
+
+    #[test]
+    fn test_state_help() -> Result<(), String> {
+        let state = VotesState;
+        assert_eq!(state.help_text().len(), 159);
+
+        Ok(())
+    }
+}
@@ -1,11 +1,20 @@
 use super::state_entrypoint_selection::EntrypointSelectionState;
+use super::state_epoch_slots::EpochSlotsState;
 use super::{State, States};
 use crate::app::Context;
 use crate::stm::events::Event;
 use crate::stm::state_debug::DebugState;
 use crate::stm::state_help::HelpState;
+use crate::stm::state_hex_dump::HexDumpState;
 use crate::stm::state_home::HomeState;
+use crate::stm::state_home_filter::HomeFilterState;
+use crate::stm::state_ledger::LedgerState;
+use crate::stm::state_node_details::NodeDetailsState;
+use crate::stm::state_snapshots::SnapshotsState;
+use crate::stm::state_subscriptions::SubscriptionsState;
 use crate::stm::state_unknown::UnknownState;
+use crate::stm::state_versions::VersionsState;
+use crate::stm::state_votes::VotesState;
 use crate::ui::core::{centered_rect, clear_box, draw_box, draw_paragraph};
 use crossterm::event::KeyCode;
 use tui::{backend::Backend, Frame};
@@ -22,6 +31,15 @@ pub struct MainStm<'a> {
     help_st: HelpState,
     entrypoints_st: EntrypointSelectionState,
     home_st: HomeState,
+    subscriptions_st: SubscriptionsState,
+    votes_st: VotesState,
+    epoch_slots_st: EpochSlotsState,
+    snapshots_st: SnapshotsState,
+    ledger_st: LedgerState,
+    versions_st: VersionsState,
+    node_details_st: NodeDetailsState,
+    home_filter_st: HomeFilterState,
+    hex_dump_st: HexDumpState,
 
     trace: bool,
 }
@@ -40,6 +58,15 @@ impl MainStm<'_> {
             help_st: HelpState,
             entrypoints_st: EntrypointSelectionState::default(),
             home_st: HomeState::default(),
+            subscriptions_st: SubscriptionsState::default(),
+            votes_st: VotesState,
+            epoch_slots_st: EpochSlotsState,
+            snapshots_st: SnapshotsState,
+            ledger_st: LedgerState,
+            versions_st: VersionsState,
+            node_details_st: NodeDetailsState,
+            home_filter_st: HomeFilterState,
+            hex_dump_st: HexDumpState,
 
             trace,
         }
@@ -66,11 +93,17 @@ impl MainStm<'_> {
                         self.home_st.on_enter_once(ctx);
                     }
                 }
+                States::Subscriptions => {
+                    if self.subscriptions_st.on_enter_first {
+                        self.subscriptions_st.on_enter_once(ctx);
+                    }
+                }
                 _ => {}
             }
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     pub fn on_event(&mut self, event: Event, ctx: &mut Context) {
         if self.current_st != States::Debug {
             ctx.debug(format!(
@@ -89,13 +122,86 @@ impl MainStm<'_> {
                 self.switch_state(States::Debug, ctx);
             }
             (
-                States::EntrypointSelection | States::Home,
+                States::EntrypointSelection
+                | States::Home
+                | States::Subscriptions
+                | States::Votes
+                | States::EpochSlots
+                | States::Snapshots
+                | States::Ledger
+                | States::Versions
+                | States::NodeDetails
+                | States::HexDump,
                 Event::Key {
                     key_code: KeyCode::Char('?'),
                 },
             ) => {
                 self.switch_state(States::Help, ctx);
             }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('s'),
+                },
+            ) => {
+                self.switch_state(States::Subscriptions, ctx);
+            }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('v'),
+                },
+            ) => {
+                self.switch_state(States::Votes, ctx);
+            }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('e'),
+                },
+            ) => {
+                self.switch_state(States::EpochSlots, ctx);
+            }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('h'),
+                },
+            ) => {
+                self.switch_state(States::Snapshots, ctx);
+            }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('l'),
+                },
+            ) => {
+                self.switch_state(States::Ledger, ctx);
+            }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('r'),
+                },
+            ) => {
+                self.switch_state(States::Versions, ctx);
+            }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('/'),
+                },
+            ) => {
+                self.switch_state(States::HomeFilter, ctx);
+            }
+            (
+                States::Home,
+                Event::Key {
+                    key_code: KeyCode::Char('x'),
+                },
+            ) => {
+                self.switch_state(States::HexDump, ctx);
+            }
             (States::Unknown, _) => {
                 if let Some(to_state) = self.unknow_st.on_event(event, ctx) {
                     self.switch_state(to_state, ctx);
@@ -116,6 +222,51 @@ impl MainStm<'_> {
                     self.switch_state(to_state, ctx);
                 }
             }
+            (States::Subscriptions, _) => {
+                if let Some(to_state) = self.subscriptions_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::Votes, _) => {
+                if let Some(to_state) = self.votes_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::EpochSlots, _) => {
+                if let Some(to_state) = self.epoch_slots_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::Snapshots, _) => {
+                if let Some(to_state) = self.snapshots_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::Ledger, _) => {
+                if let Some(to_state) = self.ledger_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::Versions, _) => {
+                if let Some(to_state) = self.versions_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::NodeDetails, _) => {
+                if let Some(to_state) = self.node_details_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::HomeFilter, _) => {
+                if let Some(to_state) = self.home_filter_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
+            (States::HexDump, _) => {
+                if let Some(to_state) = self.hex_dump_st.on_event(event, ctx) {
+                    self.switch_state(to_state, ctx);
+                }
+            }
             (States::Help, _) => {
                 if let Some(to_state) = self.help_st.on_event(event, ctx) {
                     self.switch_state(to_state, ctx);
@@ -145,6 +296,39 @@ impl MainStm<'_> {
                     self.home_st.ui(f, ctx);
                     self.home_st.help_text()
                 }
+                Some(States::Subscriptions) => {
+                    self.subscriptions_st.ui(f, ctx);
+                    self.subscriptions_st.help_text()
+                }
+                Some(States::Votes) => {
+                    self.votes_st.ui(f, ctx);
+                    self.votes_st.help_text()
+                }
+                Some(States::EpochSlots) => {
+                    self.epoch_slots_st.ui(f, ctx);
+                    self.epoch_slots_st.help_text()
+                }
+                Some(States::Snapshots) => {
+                    self.snapshots_st.ui(f, ctx);
+                    self.snapshots_st.help_text()
+                }
+                Some(States::Ledger) => {
+                    self.ledger_st.ui(f, ctx);
+                    self.ledger_st.help_text()
+                }
+                Some(States::Versions) => {
+                    self.versions_st.ui(f, ctx);
+                    self.versions_st.help_text()
+                }
+                Some(States::NodeDetails) => {
+                    self.home_st.ui(f, ctx);
+                    self.node_details_st.ui(f, ctx);
+                    self.node_details_st.help_text()
+                }
+                Some(States::HexDump) => {
+                    self.hex_dump_st.ui(f, ctx);
+                    self.hex_dump_st.help_text()
+                }
                 _ => {
                     ctx.debug(format!(
                         "[STM] draw for current:{:?} not match",
@@ -166,6 +350,21 @@ impl MainStm<'_> {
                 States::Debug => self.debug_st.ui(f, ctx),
                 States::EntrypointSelection => self.entrypoints_st.ui(f, ctx),
                 States::Home => self.home_st.ui(f, ctx),
+                States::Subscriptions => self.subscriptions_st.ui(f, ctx),
+                States::Votes => self.votes_st.ui(f, ctx),
+                States::EpochSlots => self.epoch_slots_st.ui(f, ctx),
+                States::Snapshots => self.snapshots_st.ui(f, ctx),
+                States::Ledger => self.ledger_st.ui(f, ctx),
+                States::Versions => self.versions_st.ui(f, ctx),
+                States::NodeDetails => {
+                    self.home_st.ui(f, ctx);
+                    self.node_details_st.ui(f, ctx);
+                }
+                States::HomeFilter => {
+                    self.home_st.ui(f, ctx);
+                    self.home_filter_st.ui(f, ctx);
+                }
+                States::HexDump => self.hex_dump_st.ui(f, ctx),
                 _ => ctx.debug(format!(
                     "[STM] draw for current:{:?} not match",
                     self.current_st
@@ -179,8 +378,10 @@ impl MainStm<'_> {
 mod tests {
     use super::*;
     use crossterm::event::KeyCode;
+    use solana_sdk::pubkey::Pubkey;
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn test_stm() -> Result<(), String> {
         let mut ctx = Context::new_for_testing();
 
@@ -209,6 +410,258 @@ mod tests {
         stm.on_event(e, &mut ctx);
         assert!(matches!(stm.current_st, States::Home));
 
+        // subscriptions
+        let e = Event::Key {
+            key_code: KeyCode::Char('s'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Subscriptions));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // back to subscriptions
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Subscriptions));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // votes
+        let e = Event::Key {
+            key_code: KeyCode::Char('v'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Votes));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // back to votes
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Votes));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // epoch slots
+        let e = Event::Key {
+            key_code: KeyCode::Char('e'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::EpochSlots));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // back to epoch slots
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::EpochSlots));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // snapshots
+        let e = Event::Key {
+            key_code: KeyCode::Char('h'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Snapshots));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // back to snapshots
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Snapshots));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // ledger
+        let e = Event::Key {
+            key_code: KeyCode::Char('l'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Ledger));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // back to ledger
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Ledger));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // versions
+        let e = Event::Key {
+            key_code: KeyCode::Char('r'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Versions));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // back to versions
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Versions));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // node details
+        ctx.model.home_stateful_table.push_row(vec![
+            "127.0.0.1".to_string(),
+            "0".to_string(),
+            Pubkey::new_unique().to_string(),
+        ]);
+        ctx.model.home_stateful_table.next_row();
+        let e = Event::Key {
+            key_code: KeyCode::Enter,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::NodeDetails));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // node details
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::NodeDetails));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // home filter
+        let e = Event::Key {
+            key_code: KeyCode::Char('/'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::HomeFilter));
+
+        // typed in the filter
+        let e = Event::Key {
+            key_code: KeyCode::Char('s'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::HomeFilter));
+        assert_eq!(ctx.model.home_table_view.filter, "s");
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Enter,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
+        // hex dump
+        let e = Event::Key {
+            key_code: KeyCode::Char('x'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::HexDump));
+
+        // help
+        let e = Event::Key {
+            key_code: KeyCode::Char('?'),
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Help));
+
+        // back to hex dump
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::HexDump));
+
+        // home
+        let e = Event::Key {
+            key_code: KeyCode::Esc,
+        };
+        stm.on_event(e, &mut ctx);
+        assert!(matches!(stm.current_st, States::Home));
+
         // debug
         let e = Event::Key {
             key_code: KeyCode::Char('D'),
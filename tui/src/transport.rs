@@ -1,13 +1,46 @@
 pub(crate) mod receiver;
+pub(crate) mod recorder;
+pub(crate) mod replay;
 pub(crate) mod sender;
 
-use std::time::Duration;
+use std::{sync::mpsc::Sender, time::Duration};
+
+use solana_gossip_proto::{capture::CaptureRecord, wire::Subscriptions};
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum CtrlCmd {
     Stop,
     Counter,
+    /// the message and value kinds to process from now on
+    Subscribe(Subscriptions),
+    /// record the received and sent packets, stop recording if `None`
+    Record(Option<Sender<CaptureRecord>>),
+    /// control the replay of a capture file
+    Replay(ReplayCmd),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayCmd {
+    /// pause or resume the replay
+    Pause,
+    /// replay the next packet while paused
+    Step,
+    Faster,
+    Slower,
+    /// move forward or backward by the given millis of capture time
+    Seek(i64),
+}
+
+/// The position of the replay in the capture file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayStatus {
+    /// capture time of the replay in millis
+    pub timestamp: u64,
+    pub index: usize,
+    pub total: usize,
+    pub speed: u32,
+    pub paused: bool,
 }
 
 #[derive(Debug)]
@@ -15,6 +48,7 @@ pub enum StatsId {
     Receiver,
     Sender,
     Logic,
+    Unverified,
 }
 
 pub struct Stats {
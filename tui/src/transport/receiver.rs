@@ -8,7 +8,11 @@ use std::{
 
 use log::{error, trace};
 
-use solana_gossip_proto::wire::{Payload, PACKET_DATA_SIZE};
+use solana_gossip_proto::{
+    capture::{CaptureRecord, Direction},
+    utils::since_the_epoch_millis,
+    wire::{Payload, Subscriptions, PACKET_DATA_SIZE},
+};
 
 use crate::transport::{CtrlCmd, Stats, StatsId};
 
@@ -17,11 +21,13 @@ pub(crate) fn spawn_receiver(
     tx: Sender<Payload>,
     ctrl_rx: Receiver<CtrlCmd>,
     stats_tx: Sender<Stats>,
+    mut subscriptions: Subscriptions,
 ) -> io::Result<JoinHandle<()>> {
     Builder::new()
         .name("udp_receiver_t".to_string())
         .spawn(move || {
             let mut counter: u32 = 0;
+            let mut recorder = None;
 
             'main_l: loop {
                 if let Ok(ctrl_msg) = ctrl_rx.try_recv() {
@@ -37,6 +43,9 @@ pub(crate) fn spawn_receiver(
 
                             trace!("message processed:{counter}");
                         }
+                        CtrlCmd::Subscribe(value) => subscriptions = value,
+                        CtrlCmd::Record(value) => recorder = value,
+                        CtrlCmd::Replay(_) => {}
                     }
                 }
 
@@ -49,32 +58,29 @@ pub(crate) fn spawn_receiver(
                             &buf[..len]
                         );
 
-                        let include: Vec<usize> = vec![
-                            132, // PingMessage / PongMessage
-                        ];
+                        let payload = Payload {
+                            len,
+                            buf,
+                            addr: Some(addr),
+                        };
 
-                        let exlude: Vec<usize> = vec![
-                            //254,  // LegacyContactInfo
-                            472, // Vote
-                            430, 442, 446, 454, 466, 478, 491, 503, 515, 724,
-                            185, // LowestSlot
-                            240, // SnapshotHashes
-                            200, 800,  // AccountsHashes
-                            1049, // EpochSlots
-                            1022, 1026, 1028, 1032, 1038, 1039, 163, // LegacyVersion
-                            //167,  // Version
-                            168, // NodeInstance
-                            360, // IncrementalSnapshotHashes
-                            320, 280,
-                        ];
+                        // every packet is recorded, the unsubscribed ones as well
+                        if let Some(ref recorder) = recorder {
+                            let record = CaptureRecord::new(
+                                since_the_epoch_millis(),
+                                Direction::Received,
+                                &payload,
+                            );
+                            recorder.send(record).unwrap_or(());
+                        }
 
-                        if include.contains(&len) || !exlude.contains(&len) {
-                            tx.send(Payload {
-                                len,
-                                buf,
-                                addr: Some(addr),
-                            })
-                            .unwrap_or(());
+                        // the unknown messages are left to the logic thread to report
+                        let accepted = match payload.peek_header() {
+                            Some(header) => subscriptions.accepts(&header),
+                            None => true,
+                        };
+                        if accepted {
+                            tx.send(payload).unwrap_or(());
                         }
 
                         counter += 1;
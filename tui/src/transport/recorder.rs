@@ -0,0 +1,48 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter},
+    path::Path,
+    sync::mpsc::Receiver,
+    thread::{Builder, JoinHandle},
+};
+
+use log::{error, trace};
+
+use solana_gossip_proto::{
+    capture::{CaptureRecord, CaptureWriter},
+    errors::Error,
+};
+
+/// Writes the packets recorded by the receiver and sender threads to the
+/// capture file, until all the record senders are dropped.
+pub(crate) fn spawn_recorder(
+    path: &Path,
+    rx: Receiver<CaptureRecord>,
+) -> io::Result<JoinHandle<()>> {
+    let file = BufWriter::new(File::create(path)?);
+    let mut writer = CaptureWriter::new(file).map_err(|err| match err {
+        Error::IoError(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    })?;
+
+    Builder::new()
+        .name("recorder_t".to_string())
+        .spawn(move || {
+            let mut counter: u32 = 0;
+
+            for record in rx {
+                if let Err(err) = writer.write(&record) {
+                    error!("counter:{counter} writing err:{err}");
+                    break;
+                }
+
+                counter += 1;
+            }
+
+            if let Err(err) = writer.flush() {
+                error!("counter:{counter} flushing err:{err}");
+            }
+
+            trace!("counter:{counter} terminated");
+        })
+}
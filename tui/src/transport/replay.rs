@@ -0,0 +1,262 @@
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+    sync::mpsc::{Receiver, Sender},
+    thread::{self, Builder, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use log::trace;
+
+use solana_gossip_proto::{
+    capture::{CaptureReader, CaptureRecord},
+    errors::Error,
+    wire::{Payload, Subscriptions},
+};
+
+use crate::common::Data;
+use crate::transport::{CtrlCmd, ReplayCmd, ReplayStatus, Stats, StatsId};
+
+const SPEEDS: [u32; 7] = [1, 2, 4, 8, 16, 32, 64];
+const IDLE_SLEEP: Duration = Duration::from_millis(5);
+const STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Reads all the records of a capture file.
+pub(crate) fn load_capture(path: &Path) -> io::Result<Vec<CaptureRecord>> {
+    let file = BufReader::new(File::open(path)?);
+    let to_io_error = |err| match err {
+        Error::IoError(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    };
+
+    CaptureReader::new(file)
+        .map_err(to_io_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(to_io_error)
+}
+
+/// The position in the capture, the packets are due at their original pace
+/// scaled by the speed.
+struct Cursor {
+    records: Vec<CaptureRecord>,
+    index: usize,
+    speed: usize,
+    paused: bool,
+    step: bool,
+    // the capture time reached at the instant the pace was last changed
+    anchor: (Instant, u64),
+}
+
+impl Cursor {
+    fn new(records: Vec<CaptureRecord>, now: Instant) -> Self {
+        let start = records.first().map_or(0, |record| record.timestamp);
+        Self {
+            records,
+            index: 0,
+            speed: 0,
+            paused: false,
+            step: false,
+            anchor: (now, start),
+        }
+    }
+
+    fn capture_time(&self, now: Instant) -> u64 {
+        let (instant, timestamp) = self.anchor;
+        if self.paused {
+            return timestamp;
+        }
+        let elapsed =
+            u64::try_from(now.saturating_duration_since(instant).as_millis()).unwrap_or(u64::MAX);
+        timestamp.saturating_add(elapsed.saturating_mul(u64::from(SPEEDS[self.speed])))
+    }
+
+    fn rebase(&mut self, now: Instant) {
+        self.anchor = (now, self.capture_time(now));
+    }
+
+    fn apply(&mut self, cmd: ReplayCmd, now: Instant) {
+        match cmd {
+            ReplayCmd::Pause => {
+                self.rebase(now);
+                self.paused = !self.paused;
+            }
+            ReplayCmd::Step => self.step = self.paused,
+            ReplayCmd::Faster => {
+                self.rebase(now);
+                self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+            }
+            ReplayCmd::Slower => {
+                self.rebase(now);
+                self.speed = self.speed.saturating_sub(1);
+            }
+            ReplayCmd::Seek(delta) => {
+                let time = self.capture_time(now);
+                let target = if delta < 0 {
+                    time.saturating_sub(delta.unsigned_abs())
+                } else {
+                    time.saturating_add(delta.unsigned_abs())
+                };
+                // seeking backward replays the packets again, the crds table
+                // keeps the newest values anyway
+                self.index = self
+                    .records
+                    .partition_point(|record| record.timestamp < target);
+                self.anchor = (now, target);
+            }
+        }
+    }
+
+    /// Returns the next packet due at the given instant.
+    fn next_due(&mut self, now: Instant) -> Option<&CaptureRecord> {
+        let timestamp = self.records.get(self.index)?.timestamp;
+        if self.paused {
+            if !self.step {
+                return None;
+            }
+            self.step = false;
+            self.anchor = (now, timestamp);
+        } else if timestamp > self.capture_time(now) {
+            return None;
+        }
+
+        self.index += 1;
+        self.records.get(self.index - 1)
+    }
+
+    fn status(&self, now: Instant) -> ReplayStatus {
+        ReplayStatus {
+            timestamp: self.capture_time(now),
+            index: self.index,
+            total: self.records.len(),
+            speed: SPEEDS[self.speed],
+            paused: self.paused,
+        }
+    }
+}
+
+/// Feeds the received packets of a capture to the logic thread in place of
+/// the receiver thread.
+pub(crate) fn spawn_replay(
+    records: Vec<CaptureRecord>,
+    tx: Sender<Payload>,
+    ctrl_rx: Receiver<CtrlCmd>,
+    stats_tx: Sender<Stats>,
+    data_tx: Sender<Data>,
+    mut subscriptions: Subscriptions,
+) -> io::Result<JoinHandle<()>> {
+    Builder::new().name("replay_t".to_string()).spawn(move || {
+        let mut counter: u32 = 0;
+        let mut cursor = Cursor::new(records, Instant::now());
+        let mut last_status: Option<Instant> = None;
+
+        'main_l: loop {
+            if let Ok(ctrl_msg) = ctrl_rx.try_recv() {
+                match ctrl_msg {
+                    CtrlCmd::Stop => break 'main_l,
+                    CtrlCmd::Counter => {
+                        stats_tx
+                            .send(Stats {
+                                id: StatsId::Receiver,
+                                counter,
+                            })
+                            .unwrap_or(());
+                    }
+                    CtrlCmd::Subscribe(value) => subscriptions = value,
+                    CtrlCmd::Record(_) => {}
+                    CtrlCmd::Replay(cmd) => {
+                        cursor.apply(cmd, Instant::now());
+                        last_status = None;
+                    }
+                }
+            }
+
+            let now = Instant::now();
+            if let Some(record) = cursor.next_due(now) {
+                if let Some(payload) = record.payload() {
+                    let accepted = match payload.peek_header() {
+                        Some(header) => subscriptions.accepts(&header),
+                        None => true,
+                    };
+                    if accepted {
+                        tx.send(payload).unwrap_or(());
+                    }
+                }
+
+                counter += 1;
+            } else {
+                thread::sleep(IDLE_SLEEP);
+            }
+
+            let due = match last_status {
+                Some(last_status) => now.saturating_duration_since(last_status) >= STATUS_INTERVAL,
+                None => true,
+            };
+            if due {
+                last_status = Some(now);
+                data_tx.send(Data::Replay(cursor.status(now))).unwrap_or(());
+            }
+        }
+
+        trace!("counter:{counter} terminated");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_gossip_proto::capture::Direction;
+
+    fn records(timestamps: &[u64]) -> Vec<CaptureRecord> {
+        timestamps
+            .iter()
+            .map(|timestamp| CaptureRecord {
+                timestamp: *timestamp,
+                direction: Direction::Received,
+                addr: None,
+                data: vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cursor() {
+        let start = Instant::now();
+        let mut cursor = Cursor::new(records(&[1_000, 1_000, 3_000, 20_000]), start);
+
+        // the packets are due at their original pace
+        assert_eq!(cursor.next_due(start).map(|r| r.timestamp), Some(1_000));
+        assert_eq!(cursor.next_due(start).map(|r| r.timestamp), Some(1_000));
+        assert!(cursor.next_due(start).is_none());
+        let later = start + Duration::from_secs(2);
+        assert_eq!(cursor.next_due(later).map(|r| r.timestamp), Some(3_000));
+
+        // the speed scales the pace
+        cursor.apply(ReplayCmd::Faster, later);
+        cursor.apply(ReplayCmd::Faster, later);
+        assert_eq!(cursor.status(later).speed, 4);
+        let later = later + Duration::from_secs(1);
+        assert_eq!(cursor.status(later).timestamp, 7_000);
+
+        // paused, the packets only come one step at a time
+        cursor.apply(ReplayCmd::Pause, later);
+        let later = later + Duration::from_secs(30);
+        assert!(cursor.next_due(later).is_none());
+        cursor.apply(ReplayCmd::Step, later);
+        assert_eq!(cursor.next_due(later).map(|r| r.timestamp), Some(20_000));
+        assert_eq!(cursor.status(later).timestamp, 20_000);
+        assert!(cursor.next_due(later).is_none());
+
+        // seeking moves the capture time and the position
+        cursor.apply(ReplayCmd::Seek(-18_500), later);
+        let status = cursor.status(later);
+        assert_eq!(
+            (status.timestamp, status.index, status.total),
+            (1_500, 2, 4)
+        );
+        cursor.apply(ReplayCmd::Seek(-10_000), later);
+        assert_eq!(cursor.status(later).index, 0);
+        cursor.apply(ReplayCmd::Seek(30_000), later);
+        assert_eq!(cursor.status(later).index, 4);
+    }
+}
@@ -10,7 +10,11 @@ use std::{
 
 use log::trace;
 
-use solana_gossip_proto::wire::Payload;
+use solana_gossip_proto::{
+    capture::{CaptureRecord, Direction},
+    utils::since_the_epoch_millis,
+    wire::Payload,
+};
 
 use crate::transport::{CtrlCmd, Stats, StatsId, RECV_TIMEOUT};
 
@@ -24,6 +28,7 @@ pub(crate) fn spawn_sender(
         .name("udp_sender_t".to_string())
         .spawn(move || {
             let mut counter: u32 = 0;
+            let mut recorder = None;
 
             'main_l: loop {
                 if let Ok(ctrl_msg) = ctrl_rx.try_recv() {
@@ -39,6 +44,8 @@ pub(crate) fn spawn_sender(
 
                             trace!("message processed:{counter}");
                         }
+                        CtrlCmd::Subscribe(_) | CtrlCmd::Replay(_) => {}
+                        CtrlCmd::Record(value) => recorder = value,
                     }
                 }
 
@@ -49,6 +56,15 @@ pub(crate) fn spawn_sender(
                                 trace!("counter:{counter} sending err:{err:?}");
                             }
 
+                            if let Some(ref recorder) = recorder {
+                                let record = CaptureRecord::new(
+                                    since_the_epoch_millis(),
+                                    Direction::Sent,
+                                    &data,
+                                );
+                                recorder.send(record).unwrap_or(());
+                            }
+
                             counter += 1;
                         }
                     }
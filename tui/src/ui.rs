@@ -1,3 +1,5 @@
 pub(crate) mod core;
+pub(crate) mod heatmap_widget;
+pub(crate) mod hex_dump_widget;
 pub(crate) mod list_stateful_widget;
 pub(crate) mod table_stateful_widget;
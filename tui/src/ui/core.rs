@@ -77,12 +77,79 @@ pub fn layout_columns_70_30(r: Rect) -> Vec<Rect> {
         .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
         .split(r)
 }
+
+pub fn layout_rows_30_70(r: Rect) -> Vec<Rect> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+        .split(r)
+}
+/// A coarse human readable duration, e.g. `3h12m`.
+pub fn format_duration(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h{}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d{}h", secs / 86_400, secs % 86_400 / 3600),
+    }
+}
+
+/// Formats a unix time in millis as an UTC date and time.
+pub fn format_timestamp(millis: u64) -> String {
+    let secs = millis / 1000;
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}.{:03} UTC",
+        secs % 86_400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        millis % 1000
+    )
+}
+
+// days since 1970-01-01 to the (year, month, day) date, see
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
 // tests
 #[cfg(test)]
 mod tests {
     use super::*;
     use tui::{backend::TestBackend, buffer::Buffer, Terminal};
 
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(59), "59s");
+        assert_eq!(format_duration(61), "1m");
+        assert_eq!(format_duration(7_260), "2h1m");
+        assert_eq!(format_duration(90_000), "1d1h");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00.000 UTC");
+        assert_eq!(
+            format_timestamp(1_700_000_000_123),
+            "2023-11-14 22:13:20.123 UTC"
+        );
+        assert_eq!(
+            format_timestamp(951_782_400_000),
+            "2000-02-29 00:00:00.000 UTC"
+        );
+    }
+
     #[test]
     fn test_draw_box_with_title() {
         let backend = TestBackend::new(7, 4);
@@ -0,0 +1,80 @@
+use std::collections::BTreeSet;
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+const LABEL_WIDTH: usize = 8;
+
+/// One line per row: the label followed by a cell for each slot up to
+/// `last_slot`, as many as fit in the box, highlighted when the row has it.
+pub fn draw_heatmap<B: Backend>(
+    f: &mut Frame<B>,
+    bbox: Rect,
+    title: &str,
+    rows: &[(String, &BTreeSet<u64>)],
+    last_slot: u64,
+) {
+    let num_slots = usize::from(bbox.width.saturating_sub(2)).saturating_sub(LABEL_WIDTH + 1);
+    let first_slot = last_slot.saturating_sub((num_slots as u64).saturating_sub(1));
+
+    let lines = rows
+        .iter()
+        .map(|(label, slots)| {
+            let label: String = label.chars().take(LABEL_WIDTH).collect();
+            let mut spans = vec![Span::raw(format!("{label:<LABEL_WIDTH$} "))];
+            spans.extend((first_slot..=last_slot).take(num_slots).map(|slot| {
+                if slots.contains(&slot) {
+                    Span::styled("█", Style::default().fg(Color::Green))
+                } else {
+                    Span::styled("·", Style::default().fg(Color::DarkGray))
+                }
+            }));
+            Spans::from(spans)
+        })
+        .collect::<Vec<_>>();
+
+    let paragraph =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(paragraph, bbox);
+}
+
+// tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_draw_heatmap() {
+        let backend = TestBackend::new(14, 3);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let slots = BTreeSet::from([98, 100]);
+
+        terminal
+            .draw(|f| {
+                let size = f.size();
+
+                draw_heatmap(f, size, "x", &[("abcdefghij".to_string(), &slots)], 100);
+            })
+            .unwrap();
+
+        #[rustfmt::skip]
+    let mut expected = Buffer::with_lines(vec![
+      "┌x───────────┐",
+      "│abcdefgh █·█│",
+      "└────────────┘"
+      ]);
+        expected.set_style(Rect::new(10, 1, 1, 1), Style::default().fg(Color::Green));
+        expected.set_style(Rect::new(11, 1, 1, 1), Style::default().fg(Color::DarkGray));
+        expected.set_style(Rect::new(12, 1, 1, 1), Style::default().fg(Color::Green));
+
+        terminal.backend().assert_buffer(&expected);
+    }
+}
@@ -0,0 +1,200 @@
+use solana_gossip_proto::layout::{Field, Layout};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+const BYTES_PER_LINE: usize = 16;
+const VALUE_WIDTH: usize = 12;
+
+// bytes decoded as a single value, or the tag or length preceding the
+// children of a field
+struct Segment<'a> {
+    field: &'a Field,
+    offset: usize,
+    len: usize,
+    prefix: bool,
+}
+
+// in offset order, as the fields are decoded
+fn segments<'a>(field: &'a Field, out: &mut Vec<Segment<'a>>) {
+    let Some(first) = field.children.first() else {
+        out.push(Segment {
+            field,
+            offset: field.offset,
+            len: field.len,
+            prefix: false,
+        });
+        return;
+    };
+
+    if first.offset > field.offset {
+        out.push(Segment {
+            field,
+            offset: field.offset,
+            len: first.offset - field.offset,
+            prefix: true,
+        });
+    }
+    for child in &field.children {
+        segments(child, out);
+    }
+}
+
+fn label(field: &Field) -> String {
+    match &field.value {
+        Some(value) => {
+            let value: String = value.chars().take(VALUE_WIDTH).collect();
+            format!("{}={value}", field.name)
+        }
+        None => field.name.clone(),
+    }
+}
+
+// tags and lengths, keys and wallclocks stand out, the other values alternate
+fn segment_style(index: usize, segment: &Segment) -> Style {
+    let color = if segment.prefix {
+        Color::Magenta
+    } else if matches!(segment.field.ty, Some("Pubkey" | "Hash" | "Signature")) {
+        Color::Cyan
+    } else if segment.field.name == "wallclock" {
+        Color::Yellow
+    } else if index % 2 == 1 {
+        Color::LightGreen
+    } else {
+        Color::Green
+    };
+    Style::default().fg(color)
+}
+
+/// A line per 16 bytes: the offset, the bytes colored by the field decoding
+/// them and the labels of the fields starting in the line. The bytes from the
+/// failing offset on are highlighted.
+pub fn dump_lines(data: &[u8], layout: &Layout) -> Vec<Spans<'static>> {
+    let mut segs = vec![];
+    segments(&layout.root, &mut segs);
+    let error = layout.error.as_ref();
+
+    let mut cursor = 0;
+    let mut lines = vec![];
+    for (line, chunk) in data.chunks(BYTES_PER_LINE).enumerate() {
+        let start = line * BYTES_PER_LINE;
+        let mut spans = vec![Span::raw(format!("{start:04x}  "))];
+        let mut labels = vec![];
+
+        for (i, byte) in chunk.iter().enumerate() {
+            let offset = start + i;
+            while cursor < segs.len() && segs[cursor].offset + segs[cursor].len <= offset {
+                cursor += 1;
+            }
+            let segment = segs.get(cursor).filter(|segment| segment.offset <= offset);
+
+            let style = match (error, segment) {
+                (Some(error), _) if offset == error.offset => Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+                (Some(error), _) if offset > error.offset => Style::default().fg(Color::Red),
+                (_, Some(segment)) => segment_style(cursor, segment),
+                (_, None) => Style::default().fg(Color::DarkGray),
+            };
+            spans.push(Span::styled(format!("{byte:02x}"), style));
+            spans.push(Span::raw(" "));
+
+            if let Some(segment) = segment.filter(|segment| segment.offset == offset) {
+                labels.push(label(segment.field));
+            }
+        }
+
+        let padding = (BYTES_PER_LINE - chunk.len()) * 3;
+        spans.push(Span::raw(format!("{:padding$} ", "")));
+        if let Some(error) =
+            error.filter(|error| (start..start + BYTES_PER_LINE).contains(&error.offset))
+        {
+            spans.push(Span::styled(
+                format!("<- {} ", error.message),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        spans.push(Span::raw(labels.join(" ")));
+
+        lines.push(Spans::from(spans));
+    }
+
+    lines
+}
+
+/// The failing field and error message followed by the annotated hex dump,
+/// scrolled by the given number of lines.
+pub fn draw_hex_dump<B: Backend>(
+    f: &mut Frame<B>,
+    bbox: Rect,
+    title: &str,
+    data: &[u8],
+    layout: &Layout,
+    scroll: u16,
+) {
+    let mut lines = vec![];
+    if let Some(error) = &layout.error {
+        let path = layout
+            .root
+            .path_at(error.offset.saturating_sub(1))
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" > ");
+        lines.push(Spans::from(Span::styled(
+            format!("error at offset {}: {}", error.offset, error.message),
+            Style::default().fg(Color::Red),
+        )));
+        lines.push(Spans::from(format!("decoding stopped in {path}")));
+        lines.push(Spans::default());
+    }
+    lines.extend(dump_lines(data, layout));
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .scroll((scroll, 0));
+    f.render_widget(paragraph, bbox);
+}
+
+// tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_gossip_proto::protocol::Protocol;
+
+    fn text(spans: &Spans) -> String {
+        spans.0.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_dump_lines() {
+        // a ping message truncated in the pubkey
+        let mut data = vec![4, 0, 0, 0];
+        data.extend([1; 14]);
+        let layout = Layout::of::<Protocol>(&data);
+        assert_eq!(layout.error.as_ref().map(|error| error.offset), Some(18));
+
+        let lines = dump_lines(&data, &layout);
+        assert_eq!(lines.len(), 2);
+        assert!(text(&lines[0]).starts_with("0000  04 00 00 00 01 01 "));
+        assert!(text(&lines[0]).ends_with("Protocol=PingMessage from=010101010101"));
+        assert!(text(&lines[1]).starts_with("0010  01 01"));
+        assert!(text(&lines[1]).contains("<- io error"));
+
+        // the enum tag, then the pubkey
+        assert_eq!(lines[0].0[1].style.fg, Some(Color::Magenta));
+        assert_eq!(lines[0].0[9].style.fg, Some(Color::Cyan));
+
+        // the failing offset is past the end of the packet
+        data.extend([0; 16]);
+        let lines = dump_lines(&data, &Layout::of::<Protocol>(&data[..18]));
+        assert_eq!(lines[1].0[5].style.bg, Some(Color::Red));
+        assert_eq!(lines[1].0[7].style.fg, Some(Color::Red));
+    }
+}
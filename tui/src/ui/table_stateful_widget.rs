@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 #[allow(unused_imports)]
 use tui::{
     backend::Backend,
@@ -40,6 +42,16 @@ impl<T> StatefulTable<T> {
         self.items.push(item);
     }
 
+    pub fn remove_row(&mut self, index: usize) -> Vec<T> {
+        let item = self.items.remove(index);
+        if let Some(i) = self.state.selected() {
+            if i >= self.items.len() {
+                self.state.select(self.items.len().checked_sub(1));
+            }
+        }
+        item
+    }
+
     pub fn next_row(&mut self) {
         let i = match self.state.selected() {
             Some(i) => {
@@ -84,6 +96,166 @@ impl<T> StatefulTable<T> {
     }
 }
 
+/// Sorting and filtering of the rows shown by a `StatefulTable`, the rows not
+/// matching the filter are kept aside.
+#[derive(Debug, Default)]
+pub struct TableView {
+    pub sort_column: Option<usize>,
+    pub descending: bool,
+    pub filter: String,
+    /// columns searched by the filter, all of them if empty
+    pub filter_columns: Vec<usize>,
+    /// columns sorted in the reverse order of their values, e.g. a timestamp
+    /// shown as an age
+    pub inverted_columns: Vec<usize>,
+    hidden: Vec<Vec<String>>,
+}
+
+impl TableView {
+    pub fn new(filter_columns: Vec<usize>, inverted_columns: Vec<usize>) -> Self {
+        TableView {
+            filter_columns,
+            inverted_columns,
+            ..TableView::default()
+        }
+    }
+
+    /// Sorts by the next column, no sorting after the last one.
+    pub fn next_sort_column(&mut self, num_columns: usize) {
+        self.sort_column = match self.sort_column {
+            None if num_columns > 0 => Some(0),
+            Some(column) if column + 1 < num_columns => Some(column + 1),
+            _ => None,
+        };
+    }
+
+    pub fn toggle_sort_order(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    /// Case insensitive match of the filter in any of the filter columns.
+    pub fn matches(&self, row: &[String]) -> bool {
+        if self.filter.is_empty() {
+            return true;
+        }
+        let filter = self.filter.to_lowercase();
+        let contains = |cell: &String| cell.to_lowercase().contains(&filter);
+
+        if self.filter_columns.is_empty() {
+            row.iter().any(contains)
+        } else {
+            self.filter_columns
+                .iter()
+                .filter_map(|column| row.get(*column))
+                .any(contains)
+        }
+    }
+
+    /// The number of rows, hidden ones included.
+    pub fn len(&self, table: &StatefulTable<String>) -> usize {
+        table.items.len() + self.hidden.len()
+    }
+
+    /// Replaces, adds or removes the row identified by `id` in the given column.
+    pub fn update_row(
+        &mut self,
+        table: &mut StatefulTable<String>,
+        column: usize,
+        id: &str,
+        row: Option<Vec<String>>,
+    ) {
+        if let Some(index) = self.hidden.iter().position(|row| row[column] == id) {
+            match row {
+                Some(row) => self.hidden[index] = row,
+                None => {
+                    self.hidden.swap_remove(index);
+                }
+            }
+            return;
+        }
+
+        let at_index = table.items.iter().position(|row| row[column] == id);
+        match (at_index, row) {
+            (Some(index), Some(row)) => table.items[index] = row,
+            (None, Some(row)) => table.push_row(row),
+            (Some(index), None) => {
+                table.remove_row(index);
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// Moves the rows in or out of the table according to the filter and sorts
+    /// the shown ones, the selected row stays selected if still shown.
+    pub fn apply(&mut self, table: &mut StatefulTable<String>) {
+        let selected = table
+            .state
+            .selected()
+            .and_then(|index| table.items.get(index))
+            .cloned();
+
+        let mut rows = std::mem::take(&mut table.items);
+        rows.append(&mut self.hidden);
+        let (mut shown, hidden) = rows.into_iter().partition(|row| self.matches(row));
+        self.hidden = hidden;
+
+        if let Some(column) = self.sort_column {
+            let descending = self.descending != self.inverted_columns.contains(&column);
+            shown.sort_by(|a: &Vec<String>, b: &Vec<String>| {
+                let ordering = match (a.get(column), b.get(column)) {
+                    (Some(a), Some(b)) => natural_cmp(a, b),
+                    (a, b) => a.cmp(&b),
+                };
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        table.items = shown;
+        let index =
+            selected.and_then(|selected| table.items.iter().position(|row| *row == selected));
+        table.state.select(index);
+    }
+}
+
+/// Compares the digit runs by their numeric value and the rest as text, so
+/// that ports, ages, addresses and versions are in their natural order.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    fn chunks(s: &str) -> Vec<&str> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        for (index, c) in s.char_indices().skip(1) {
+            let previous = s[..index].chars().next_back().unwrap_or(c);
+            if previous.is_ascii_digit() != c.is_ascii_digit() {
+                chunks.push(&s[start..index]);
+                start = index;
+            }
+        }
+        if start < s.len() {
+            chunks.push(&s[start..]);
+        }
+        chunks
+    }
+
+    for (a, b) in chunks(a).into_iter().zip(chunks(b)) {
+        let is_number = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        let ordering = if is_number(a) && is_number(b) {
+            let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        } else {
+            a.cmp(b)
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 pub fn draw_stateful_table<B: Backend>(
     f: &mut Frame<B>,
     bbox: Rect,
@@ -92,6 +264,23 @@ pub fn draw_stateful_table<B: Backend>(
     widths: &[Constraint],
     stateful_table: &mut StatefulTable<String>,
 ) {
+    draw_stateful_table_with(f, bbox, title, headers, widths, stateful_table, |row| {
+        Row::new(row.iter().map(|c| Cell::from(c.to_string())))
+    });
+}
+
+/// Draws the table turning each stored row into the shown one.
+pub fn draw_stateful_table_with<B: Backend, F>(
+    f: &mut Frame<B>,
+    bbox: Rect,
+    title: &str,
+    headers: &[&str],
+    widths: &[Constraint],
+    stateful_table: &mut StatefulTable<String>,
+    format_row: F,
+) where
+    F: Fn(&[String]) -> Row<'static>,
+{
     let selected_style = Style::default().add_modifier(Modifier::REVERSED);
     let header_style = Style::default().bg(Color::LightBlue);
     let header_cells = headers
@@ -103,10 +292,10 @@ pub fn draw_stateful_table<B: Backend>(
         .height(1)
         .bottom_margin(1);
 
-    let rows = stateful_table.items.iter().map(|row| {
-        let cells = row.iter().map(|c| Cell::from(c.to_string()));
-        Row::new(cells).height(1_u16).bottom_margin(0)
-    });
+    let rows = stateful_table
+        .items
+        .iter()
+        .map(|row| format_row(row).height(1_u16).bottom_margin(0));
 
     let t = Table::new(rows)
         .header(header)
@@ -158,6 +347,24 @@ mod tests {
         assert_eq!(stateful_table.state.selected(), None);
     }
 
+    #[test]
+    fn test_stateful_table_remove_row() {
+        let mut stateful_table = StatefulTable::default();
+        stateful_table.push_row(vec!["Hello"]);
+        stateful_table.push_row(vec!["World"]);
+
+        stateful_table.previous_row();
+        stateful_table.previous_row();
+        assert_eq!(stateful_table.state.selected(), Some(1));
+
+        assert_eq!(stateful_table.remove_row(1), vec!["World"]);
+        assert_eq!(stateful_table.state.selected(), Some(0));
+
+        assert_eq!(stateful_table.remove_row(0), vec!["Hello"]);
+        assert_eq!(stateful_table.state.selected(), None);
+        assert!(stateful_table.items.is_empty());
+    }
+
     #[test]
     fn test_stateful_table_reverse() {
         let mut stateful_table = StatefulTable::default();
@@ -212,6 +419,67 @@ mod tests {
         assert_eq!(stateful_table.state.selected(), None);
     }
 
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("9", "10"), Ordering::Less);
+        assert_eq!(natural_cmp("8001", "8001"), Ordering::Equal);
+        assert_eq!(natural_cmp("9.1.1.1", "10.0.0.2"), Ordering::Less);
+        assert_eq!(
+            natural_cmp("1.18.10 abcd", "1.18.9 abcd"),
+            Ordering::Greater
+        );
+        assert_eq!(natural_cmp("503 [!]", "503"), Ordering::Greater);
+        assert_eq!(natural_cmp("abc", "abd"), Ordering::Less);
+        assert_eq!(natural_cmp("007", "7"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_table_view() {
+        let row = |ip: &str, port: &str| vec![ip.to_string(), port.to_string()];
+        let mut table = StatefulTable::default();
+        let mut view = TableView::new(vec![0], vec![]);
+
+        view.update_row(&mut table, 0, "10.0.0.1", Some(row("10.0.0.1", "8001")));
+        view.update_row(&mut table, 0, "9.0.0.1", Some(row("9.0.0.1", "10000")));
+        view.update_row(&mut table, 0, "10.0.0.2", Some(row("10.0.0.2", "900")));
+
+        view.next_sort_column(2);
+        view.apply(&mut table);
+        assert_eq!(table.items[0][0], "9.0.0.1");
+
+        view.next_sort_column(2);
+        table.next_row();
+        view.apply(&mut table);
+        assert_eq!(table.items[0][1], "900");
+        // the selection follows the row
+        assert_eq!(table.state.selected(), Some(2));
+
+        view.toggle_sort_order();
+        view.apply(&mut table);
+        assert_eq!(table.items[0][1], "10000");
+        assert_eq!(table.state.selected(), Some(0));
+
+        view.filter = "10.0".to_string();
+        view.apply(&mut table);
+        assert_eq!(table.items.len(), 2);
+        assert_eq!(view.len(&table), 3);
+        assert_eq!(table.state.selected(), None);
+
+        // the hidden rows are updated as well
+        view.update_row(&mut table, 0, "9.0.0.1", None);
+        view.filter.clear();
+        view.apply(&mut table);
+        assert_eq!(table.items.len(), 2);
+
+        // the ports as if they were timestamps
+        view.inverted_columns = vec![1];
+        view.apply(&mut table);
+        assert_eq!(table.items[0][1], "900");
+
+        view.next_sort_column(2);
+        assert_eq!(view.sort_column, None);
+    }
+
     #[test]
     fn test_stateful_table_with_title() {
         let backend = TestBackend::new(22, 6);